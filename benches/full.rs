@@ -4,26 +4,45 @@ extern crate rln;
 use crate::rln::{
     circuit::{Circuit},
     merkle::IncrementalTree,
-    client::{calculate_output},
-    poseidon::{Hash, P128Pow5T3, ConstantLength},
+    merkle::quaternary::QuaternaryTree,
+    client::{calculate_output, rate_commitment},
     proof::{Proof, Instance},
     keys::{ProvingKey}
 };
 
 use rand;
 use std::convert::TryInto;
+use std::marker::PhantomData;
 use ff::Field;
+use pasta_curves::{pallas, vesta};
 use crate::rln::halo2::{
     pasta::Fp,
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk,
+    plonk::{Advice, Column, ConstraintSystem, Error, Instance as InstanceColumn},
+    poly::commitment::Params,
+    transcript::Blake2bWrite,
 };
+use crate::rln::utils::{UtilitiesInstructions, NumericCell};
+use crate::rln::poseidon::{P128Pow5T3, P128Pow5T5};
+use crate::rln::gadget::poseidon::{Pow5T3Chip, Pow5Chip};
+use crate::rln::gadget::merkle::{MerkleChip, MerkleConfig, InclusionProof};
+use crate::rln::gadget::quaternary::{QuaternaryMerkleChip, QuaternaryMerkleConfig, QuaternaryInclusionProof};
 
-fn bench_rln(depth: usize, c: &mut Criterion) {
+// binary and 4-ary trees with the same capacity of 2^30 leaves
+const BINARY_DEPTH: usize = 30;
+const QUATERNARY_DEPTH: usize = 15;
+
+fn bench_rln<const DEPTH: usize>(c: &mut Criterion) {
     let mut rng = rand::thread_rng();
-    let mut tree = IncrementalTree::new(Fp::zero(), depth);
+    let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
     let k = 11;
 
     let secret = Fp::random(&mut rng);
-    let commitment = Hash::init(P128Pow5T3, ConstantLength::<1>).hash([secret]);
+    let message_id = Fp::from(0);
+    let user_message_limit = Fp::from(10);
+    let commitment = rate_commitment(secret, user_message_limit);
 
     let num_of_leaves = 5;
 
@@ -33,44 +52,260 @@ fn bench_rln(depth: usize, c: &mut Criterion) {
 
     tree.insert(commitment);
 
-    let (siblings, pos) = tree.witness(commitment);
-
-    let pos: Vec<Option<bool>> = pos.iter().map(|pos| Some(*pos)).collect();
-    let siblings: Vec<Option<Fp>> = siblings.iter().map(|sibling| Some(*sibling)).collect();
+    let (siblings, pos) = tree.proof(commitment).unwrap().circuit_witness::<DEPTH>().unwrap();
 
     let epoch = Fp::random(&mut rng);
+    let rln_identifier = Fp::random(&mut rng);
     let msg = "hello rln";
-    let (y, nullifier, signal) = calculate_output(secret, epoch, msg);
+    let (y, nullifier, signal) = calculate_output(secret, epoch, rln_identifier, message_id, msg);
 
-    let pk = ProvingKey::build(k);
+    let pk = ProvingKey::<DEPTH>::build(k);
     // let prover_name = name.to_string() + "-prover";
 
-    let circuit = Circuit {
+    let circuit = Circuit::<DEPTH> {
         secret: Some(secret),
         signal: Some(signal),
-        siblings: siblings.clone().try_into().expect("siblings with incorrect length"),
-        pos: pos.clone().try_into().expect("pos with incorrect length"),
-        epoch: Some(epoch)
+        siblings,
+        pos,
+        epoch: Some(epoch),
+        rln_identifier: Some(rln_identifier),
+        message_id: Some(message_id),
+        user_message_limit: Some(user_message_limit)
     };
 
     let instance = Instance {
         y, 
         nullifier,
         signal, 
-        root: tree.root()
+        root: tree.root(),
+        rln_identifier,
+        epoch
     };
 
     let mut group = c.benchmark_group("rln-proof");
     group.sample_size(10);
-    group.bench_function("full", |b| {
+    group.bench_function(format!("full-{}", DEPTH), |b| {
         b.iter(|| {
             Proof::create(&pk, &[circuit.clone()], &[instance.clone()]).expect("proof should not fail")
         });
     });
 }
 
+#[derive(Clone, Debug)]
+struct BinaryConfig {
+    advice: [Column<Advice>; 4],
+    instance: Column<InstanceColumn>,
+    merkle_config: MerkleConfig<pallas::Base>
+}
+
+/// Inclusion of a leaf in a binary tree, the root is the only public input.
+#[derive(Clone, Debug, Default)]
+struct BinaryCircuit {
+    leaf: Option<Fp>,
+    siblings: [Option<Fp>; BINARY_DEPTH],
+    leaf_pos: [Option<bool>; BINARY_DEPTH]
+}
+
+impl UtilitiesInstructions<pallas::Base> for BinaryCircuit {
+    type Var = NumericCell<pallas::Base>;
+}
+
+impl plonk::Circuit<pallas::Base> for BinaryCircuit {
+    type Config = BinaryConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        let advice = [(); 4].map(|_| meta.advice_column());
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance.into());
+
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let rc_a = [(); 3].map(|_| meta.fixed_column());
+        let rc_b = [(); 3].map(|_| meta.fixed_column());
+
+        meta.enable_constant(rc_b[0]);
+
+        let poseidon_config = Pow5T3Chip::configure(meta, P128Pow5T3, advice[0..3].try_into().unwrap(), advice[3], rc_a, rc_b);
+        let merkle_config = MerkleChip::configure(meta, advice[0..3].try_into().unwrap(), poseidon_config);
+
+        BinaryConfig {
+            advice,
+            instance,
+            merkle_config
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        let leaf = self.load_private(layouter.namespace(|| "witness leaf"), config.advice[0], self.leaf)?;
+
+        let inclusion_proof = InclusionProof {
+            merkle_chip: MerkleChip::construct(config.merkle_config.clone()),
+            siblings: self.siblings,
+            leaf_pos: self.leaf_pos,
+            _marker: PhantomData::<pallas::Base>
+        };
+
+        let root = inclusion_proof.calculate_root(layouter.namespace(|| "merkle root"), leaf)?;
+        self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, 0)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct QuaternaryConfig {
+    advice: [Column<Advice>; 6],
+    instance: Column<InstanceColumn>,
+    merkle_config: QuaternaryMerkleConfig
+}
+
+/// Inclusion of a leaf in a 4-ary tree, the root is the only public input.
+#[derive(Clone, Debug, Default)]
+struct QuaternaryCircuit {
+    leaf: Option<Fp>,
+    siblings: [[Option<Fp>; 3]; QUATERNARY_DEPTH],
+    leaf_pos: [Option<u8>; QUATERNARY_DEPTH]
+}
+
+impl UtilitiesInstructions<pallas::Base> for QuaternaryCircuit {
+    type Var = NumericCell<pallas::Base>;
+}
+
+impl plonk::Circuit<pallas::Base> for QuaternaryCircuit {
+    type Config = QuaternaryConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        let advice = [(); 6].map(|_| meta.advice_column());
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance.into());
+
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let rc_a = [(); 5].map(|_| meta.fixed_column());
+        let rc_b = [(); 5].map(|_| meta.fixed_column());
+
+        meta.enable_constant(rc_b[0]);
+
+        let poseidon_config = Pow5Chip::configure(meta, P128Pow5T5, advice[0..5].try_into().unwrap(), advice[5], rc_a, rc_b);
+        let merkle_config = QuaternaryMerkleChip::configure(meta, advice[0..5].try_into().unwrap(), poseidon_config);
+
+        QuaternaryConfig {
+            advice,
+            instance,
+            merkle_config
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        let leaf = self.load_private(layouter.namespace(|| "witness leaf"), config.advice[0], self.leaf)?;
+
+        let inclusion_proof = QuaternaryInclusionProof {
+            merkle_chip: QuaternaryMerkleChip::construct(config.merkle_config.clone()),
+            siblings: self.siblings,
+            leaf_pos: self.leaf_pos,
+        };
+
+        let root = inclusion_proof.calculate_root(layouter.namespace(|| "merkle root"), leaf)?;
+        self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, 0)
+    }
+}
+
+/// Smallest k the circuit fits in and is satisfied with.
+fn min_k<C: plonk::Circuit<pallas::Base>>(circuit: &C, root: Fp) -> u32 {
+    (4..=20)
+        .find(|k| {
+            MockProver::run(*k, circuit, vec![vec![root]])
+                .map(|prover| prover.verify().is_ok())
+                .unwrap_or(false)
+        })
+        .expect("circuit does not fit in 2^20 rows")
+}
+
+fn bench_inclusion_circuit<C: plonk::Circuit<pallas::Base> + Clone + Default>(
+    c: &mut Criterion,
+    name: &str,
+    circuit: C,
+    root: Fp,
+) {
+    let k = min_k(&circuit, root);
+
+    let params = Params::<vesta::Affine>::new(k);
+    let vk = plonk::keygen_vk(&params, &C::default()).unwrap();
+    let pk = plonk::keygen_pk(&params, vk, &C::default()).unwrap();
+
+    let mut group = c.benchmark_group("inclusion-proof");
+    group.sample_size(10);
+    group.bench_function(format!("{}-k{}", name, k), |b| {
+        b.iter(|| {
+            let mut transcript = Blake2bWrite::<_, vesta::Affine, _>::init(vec![]);
+            plonk::create_proof(&params, &pk, &[circuit.clone()], &[&[&[root]]], &mut transcript)
+                .expect("proof generation should not fail")
+        });
+    });
+}
+
+/// Compares proving inclusion in a binary tree against a 4-ary tree of the same capacity.
+fn bench_arity(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let leaves: Vec<Fp> = (0..6).map(|_| Fp::random(&mut rng)).collect();
+    let leaf = leaves[5];
+
+    let mut tree = IncrementalTree::new(Fp::zero(), BINARY_DEPTH);
+    for leaf in leaves.iter() {
+        tree.insert(*leaf);
+    }
+    let (siblings, leaf_pos) = tree.proof(leaf).unwrap().circuit_witness::<BINARY_DEPTH>().unwrap();
+
+    let circuit = BinaryCircuit {
+        leaf: Some(leaf),
+        siblings,
+        leaf_pos
+    };
+    bench_inclusion_circuit(c, &format!("binary-{}", BINARY_DEPTH), circuit, tree.root());
+
+    let mut tree = QuaternaryTree::new(Fp::zero(), QUATERNARY_DEPTH);
+    for leaf in leaves.iter() {
+        tree.insert(*leaf);
+    }
+    let (siblings, pos) = tree.witness(leaf).unwrap();
+
+    let siblings: Vec<[Option<Fp>; 3]> = siblings.iter().map(|siblings| siblings.map(Some)).collect();
+    let pos: Vec<Option<u8>> = pos.iter().map(|pos| Some(*pos)).collect();
+
+    let circuit = QuaternaryCircuit {
+        leaf: Some(leaf),
+        siblings: siblings.try_into().expect("siblings with incorrect length"),
+        leaf_pos: pos.try_into().expect("pos with incorrect length")
+    };
+    bench_inclusion_circuit(c, &format!("quaternary-{}", QUATERNARY_DEPTH), circuit, tree.root());
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    bench_rln(30, c);
+    bench_rln::<16>(c);
+    bench_rln::<30>(c);
+    bench_arity(c);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 
 extern crate rln;
 use crate::rln::{
@@ -139,10 +139,7 @@ fn bench_merkle(c: &mut Criterion) {
     tree.insert(Fp::from(7));
 
     let leaf = Fp::from(6);
-    let (siblings, pos) = tree.witness(leaf);
-
-    let pos: Vec<Option<bool>> = pos.iter().map(|pos| Some(*pos)).collect();
-    let siblings: Vec<Option<Fp>> = siblings.iter().map(|sibling| Some(*sibling)).collect();
+    let (siblings, leaf_pos) = tree.proof(leaf).unwrap().circuit_witness::<DEPTH>().unwrap();
 
     let params = rln::halo2::poly::commitment::Params::<vesta::Affine>::new(k);
     let empty_circuit: Circuit = Default::default();
@@ -152,9 +149,9 @@ fn bench_merkle(c: &mut Criterion) {
 
     let circuit = Circuit {
         leaf: Some(leaf),
-        siblings: siblings.try_into().expect("siblings with incorrect length"),
+        siblings,
         root: Some(tree.root()), 
-        leaf_pos: pos.try_into().expect("siblings with incorrect length")
+        leaf_pos
     };
 
     let mut group = c.benchmark_group("merkle-proof");
@@ -169,8 +166,38 @@ fn bench_merkle(c: &mut Criterion) {
 
 }
 
+fn bench_insert(c: &mut Criterion) {
+    let leaves: Vec<Fp> = (1..=256u64).map(Fp::from).collect();
+
+    let mut group = c.benchmark_group("merkle-insert");
+    group.sample_size(10);
+    group.bench_function("insert-loop-256", |b| {
+        b.iter_batched(
+            || IncrementalTree::new(Fp::zero(), DEPTH),
+            |mut tree| {
+                for leaf in leaves.iter() {
+                    tree.insert(*leaf);
+                }
+                tree
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    group.bench_function("insert-batch-256", |b| {
+        b.iter_batched(
+            || IncrementalTree::new(Fp::zero(), DEPTH),
+            |mut tree| {
+                tree.insert_batch(&leaves).expect("batch fits in the tree");
+                tree
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     bench_merkle(c);
+    bench_insert(c);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,212 @@
+/*
+Self-contained encoding of an RLN proof together with its public inputs.
+
+version           u8
+depth             u32 little-endian
+key fingerprint   32 bytes, see `VerifyingKey::fingerprint`
+public inputs     canonical encoding of `Instance`, 6 field elements of 32 bytes
+proof length      u32 little-endian
+proof             transcript bytes
+
+Field elements are in their canonical little-endian encoding and decoding rejects
+values at or above the modulus, so every bundle has exactly one encoding.
+*/
+use std::io::{self, Read};
+
+use crate::{
+    error::Error,
+    keys::{ProvingKey, VerifyingKey},
+    merkle::RootPolicy,
+    proof::{Instance, Proof, INSTANCE_LEN},
+    circuit::Circuit,
+};
+
+/// Version of the bundle format.
+pub const BUNDLE_FORMAT_VERSION: u8 = 1;
+
+/// A proof with everything a verifier needs besides the key and the accepted roots.
+#[derive(Clone, Debug)]
+pub struct RlnProofBundle<const DEPTH: usize> {
+    pub key_fingerprint: [u8; 32],
+    pub instance: Instance,
+    pub proof: Proof<DEPTH>,
+}
+
+impl<const DEPTH: usize> RlnProofBundle<DEPTH> {
+    /// Proves `circuit` for `instance` and bundles the proof.
+    pub fn create(pk: &ProvingKey<DEPTH>, circuit: Circuit<DEPTH>, instance: Instance) -> Result<Self, Error> {
+        let proof = Proof::create(pk, &[circuit], &[instance.clone()])?;
+        Ok(RlnProofBundle {
+            key_fingerprint: pk.fingerprint(),
+            instance,
+            proof,
+        })
+    }
+
+    /// Verifies the bundled proof with `vk`, whose fingerprint must be the bundled one.
+    pub fn verify<P: RootPolicy + ?Sized>(&self, vk: &VerifyingKey<DEPTH>, roots: &P) -> Result<(), Error> {
+        if self.key_fingerprint != vk.fingerprint() {
+            return Err(Error::InvalidBundle("bundle was made for another key"));
+        }
+        self.proof.verify(vk, &[self.instance.clone()], roots)
+    }
+
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&[BUNDLE_FORMAT_VERSION])?;
+        writer.write_all(&(DEPTH as u32).to_le_bytes())?;
+        writer.write_all(&self.key_fingerprint)?;
+        writer.write_all(&self.instance.to_bytes())?;
+
+        let proof = self.proof.as_ref();
+        if proof.len() > u32::MAX as usize {
+            return Err(Error::InvalidBundle("proof is too long"));
+        }
+        writer.write_all(&(proof.len() as u32).to_le_bytes())?;
+        writer.write_all(proof)?;
+        Ok(())
+    }
+
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != BUNDLE_FORMAT_VERSION {
+            return Err(Error::InvalidBundle("unsupported bundle format version"));
+        }
+
+        let mut depth = [0u8; 4];
+        reader.read_exact(&mut depth)?;
+        if u32::from_le_bytes(depth) as usize != DEPTH {
+            return Err(Error::InvalidBundle("bundle was made for a different tree depth"));
+        }
+
+        let mut key_fingerprint = [0u8; 32];
+        reader.read_exact(&mut key_fingerprint)?;
+
+        let mut instance = [0u8; INSTANCE_LEN];
+        reader.read_exact(&mut instance)?;
+        let instance = Instance::from_bytes(&instance)
+            .map_err(|_| Error::InvalidBundle("non-canonical field element"))?;
+
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as u64;
+
+        // read through `take` so a forged length does not allocate up front
+        let mut proof = vec![];
+        reader.take(len).read_to_end(&mut proof)?;
+        if proof.len() as u64 != len {
+            return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        Ok(RlnProofBundle {
+            key_fingerprint,
+            instance,
+            proof: Proof::new(proof),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write(&mut bytes).expect("writing to a vec does not fail");
+        bytes
+    }
+
+    /// Decodes a bundle, rejecting trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+        let bundle = Self::read(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidBundle("trailing bytes"));
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ff::{Field, PrimeField};
+    use pasta_curves::pallas::Base as Fp;
+
+    use crate::client::{rate_commitment, SignalRequest};
+    use crate::keys::{ProvingKey, VerifyingKey};
+    use crate::merkle::IncrementalTree;
+    use crate::error::Error;
+
+    use super::{RlnProofBundle, BUNDLE_FORMAT_VERSION};
+
+    const K: u32 = 11;
+    const DEPTH: usize = 16;
+
+    // offset of the first public input
+    const INPUTS: usize = 1 + 4 + 32;
+
+    fn bundle(pk: &ProvingKey<DEPTH>) -> RlnProofBundle<DEPTH> {
+        let mut rng = rand::thread_rng();
+        let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
+
+        let request = SignalRequest {
+            secret: Fp::random(&mut rng),
+            user_message_limit: Fp::from(1),
+            message_id: Fp::from(0),
+            epoch: Fp::random(&mut rng),
+            rln_identifier: Fp::random(&mut rng),
+            signal: "hello rln".to_string(),
+        };
+        tree.insert(rate_commitment(request.secret, request.user_message_limit));
+
+        let (circuit, instance) = request.witness(&tree).unwrap();
+        RlnProofBundle::create(pk, circuit, instance).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let vk = VerifyingKey::<DEPTH>::build(K);
+        assert_eq!(pk.fingerprint(), vk.fingerprint());
+
+        let bundle = bundle(&pk);
+        let root = bundle.instance.root;
+        assert!(bundle.verify(&vk, &root).is_ok());
+
+        let bytes = bundle.to_bytes();
+        assert_eq!(bytes[0], BUNDLE_FORMAT_VERSION);
+        let decoded = RlnProofBundle::<DEPTH>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(decoded.verify(&vk, &root).is_ok());
+
+        let mut other_key = decoded.clone();
+        other_key.key_fingerprint[0] ^= 1;
+        assert!(matches!(other_key.verify(&vk, &root), Err(Error::InvalidBundle(_))));
+
+        let mut tampered = bytes.clone();
+        tampered[INPUTS] ^= 1;
+        let tampered = RlnProofBundle::<DEPTH>::from_bytes(&tampered).unwrap();
+        assert!(matches!(tampered.verify(&vk, &root), Err(Error::VerificationFailed)));
+    }
+
+    #[test]
+    fn reject_malformed() {
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let bytes = bundle(&pk).to_bytes();
+
+        let mut version = bytes.clone();
+        version[0] = BUNDLE_FORMAT_VERSION + 1;
+        assert!(matches!(RlnProofBundle::<DEPTH>::from_bytes(&version), Err(Error::InvalidBundle(_))));
+
+        assert!(matches!(RlnProofBundle::<20>::from_bytes(&bytes), Err(Error::InvalidBundle(_))));
+
+        // the modulus itself is not a canonical encoding
+        let mut modulus = bytes.clone();
+        let p_minus_one = (-Fp::one()).to_repr();
+        modulus[INPUTS..INPUTS + 32].copy_from_slice(&p_minus_one);
+        modulus[INPUTS] += 1;
+        assert!(matches!(RlnProofBundle::<DEPTH>::from_bytes(&modulus), Err(Error::InvalidBundle(_))));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(RlnProofBundle::<DEPTH>::from_bytes(&trailing), Err(Error::InvalidBundle(_))));
+
+        assert!(matches!(RlnProofBundle::<DEPTH>::from_bytes(&bytes[..bytes.len() - 1]), Err(Error::Io(_))));
+        assert!(matches!(RlnProofBundle::<DEPTH>::from_bytes(&bytes[..INPUTS]), Err(Error::Io(_))));
+    }
+}
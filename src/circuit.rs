@@ -8,22 +8,26 @@ use std::marker::PhantomData;
 use pasta_curves::pallas;
 
 use crate:: {
-    utils::{UtilitiesInstructions, NumericCell, CellValue, Var, from_cell_vale_to_numeric},
+    utils::{UtilitiesInstructions, NumericCell, CellValue, from_cell_vale_to_numeric},
     gadget::{
-        poseidon::{Pow5T3Chip as PoseidonChip, Pow5T3Config as PoseidonConfig, Hash as PoseidonHash},
+        poseidon::{Pow5T3Chip as PoseidonChip, Pow5T3Config as PoseidonConfig, Hash as PoseidonHash, HashInstruction},
         rln::{RlnChip, RlnConfig, RlnInstructions},
-        merkle::{MerkleChip, MerkleConfig, InclusionProof}
+        merkle::{MerkleChip, MerkleConfig, InclusionProof},
+        range::{RangeCheckChip, RangeCheckConfig, RangeCheckInstructions}
     },
     poseidon::{ConstantLength, P128Pow5T3}
 };
 
-pub const DEPTH: usize = 30;
-
 // Absolute offsets for public inputs.
 pub const Y: usize = 0;
 pub const NULLIFIER: usize = 1;
 pub const SIGNAL: usize = 2;
 pub const ROOT: usize = 3;
+pub const RLN_IDENTIFIER: usize = 4;
+pub const EPOCH: usize = 5;
+
+/// Number of bits available to `message_id` and `user_message_limit - message_id - 1`.
+pub const MESSAGE_LIMIT_BITS: usize = 32;
 
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -31,24 +35,43 @@ pub struct Config {
     instance: Column<Instance>,
     poseidon_config: PoseidonConfig<Fp>,
     merkle_config: MerkleConfig<Fp>,
-    rln_config: RlnConfig<Fp>
+    rln_config: RlnConfig<Fp>,
+    range_config: RangeCheckConfig
 }
 
 
-#[derive(Clone, Debug, Default)]
-pub struct Circuit {
+#[derive(Clone, Debug)]
+pub struct Circuit<const DEPTH: usize> {
     pub secret: Option<Fp>,
     pub signal: Option<Fp>,
     pub siblings: [Option<Fp>; DEPTH],
     pub pos: [Option<bool>; DEPTH],
-    pub epoch: Option<Fp>
+    pub epoch: Option<Fp>,
+    pub rln_identifier: Option<Fp>,
+    pub message_id: Option<Fp>,
+    pub user_message_limit: Option<Fp>
 }
 
-impl UtilitiesInstructions<pallas::Base> for Circuit {
+impl<const DEPTH: usize> Default for Circuit<DEPTH> {
+    fn default() -> Self {
+        Circuit {
+            secret: None,
+            signal: None,
+            siblings: [None; DEPTH],
+            pos: [None; DEPTH],
+            epoch: None,
+            rln_identifier: None,
+            message_id: None,
+            user_message_limit: None
+        }
+    }
+}
+
+impl<const DEPTH: usize> UtilitiesInstructions<pallas::Base> for Circuit<DEPTH> {
     type Var = NumericCell<pallas::Base>;
 }
 
-impl plonk::Circuit<pallas::Base> for Circuit {
+impl<const DEPTH: usize> plonk::Circuit<pallas::Base> for Circuit<DEPTH> {
     type Config = Config;
     type FloorPlanner = SimpleFloorPlanner;
 
@@ -88,13 +111,15 @@ impl plonk::Circuit<pallas::Base> for Circuit {
         let poseidon_config = PoseidonChip::configure(meta, P128Pow5T3, advice[0..3].try_into().unwrap(), advice[3], rc_a, rc_b);
         let rln_config = RlnChip::configure(meta, advice[0..3].try_into().unwrap(), poseidon_config.clone());
         let merkle_config = MerkleChip::<pallas::Base>::configure(meta, advice[0..3].try_into().unwrap(), poseidon_config.clone());
+        let range_config = RangeCheckChip::<pallas::Base>::configure(meta, advice[0..3].try_into().unwrap());
 
         Config {
             advice, 
             instance,
             poseidon_config,
             merkle_config,
-            rln_config
+            rln_config,
+            range_config
         }
     }
 
@@ -123,8 +148,35 @@ impl plonk::Circuit<pallas::Base> for Circuit {
             self.signal,
         )?;
 
+        let rln_identifier = self.load_private(
+            layouter.namespace(|| "witness rln_identifier"),
+            config.advice[0],
+            self.rln_identifier,
+        )?;
+
+        let message_id = self.load_private(
+            layouter.namespace(|| "witness message_id"),
+            config.advice[0],
+            self.message_id,
+        )?;
+
+        let user_message_limit = self.load_private(
+            layouter.namespace(|| "witness user_message_limit"),
+            config.advice[0],
+            self.user_message_limit,
+        )?;
+
+        let range_chip = RangeCheckChip::construct(config.range_config);
+        range_chip.less_than(
+            layouter.namespace(|| "message_id < user_message_limit"),
+            message_id.clone(),
+            user_message_limit.clone(),
+            MESSAGE_LIMIT_BITS
+        )?;
+
         let rln_chip = RlnChip::construct(config.rln_config);
-        let (y, k) = rln_chip.calculate_output(layouter.namespace(|| "calculate y"), secret.clone(), epoch, signal.clone())?;
+        let external_nullifier = rln_chip.calculate_external_nullifier(layouter.namespace(|| "calculate external nullifier"), epoch.clone(), rln_identifier.clone())?;
+        let (y, k) = rln_chip.calculate_output(layouter.namespace(|| "calculate y"), secret.clone(), external_nullifier, message_id, signal.clone())?;
         let nullifier = rln_chip.calculate_nullifier(layouter.namespace(|| "calculate nullifier"), k.clone())?;
 
         let poseidon_config = config.poseidon_config;
@@ -144,7 +196,14 @@ impl plonk::Circuit<pallas::Base> for Circuit {
 
         let word = poseidon_hasher.hash(layouter.namespace(|| "wtns"), message)?;
         let digest: CellValue<Fp> = word.inner().into();
-        let commitment = from_cell_vale_to_numeric(layouter.namespace(|| "dummy conf"), config.advice[0], digest.value())?;
+        let commitment = from_cell_vale_to_numeric(layouter.namespace(|| "dummy conf"), config.advice[0], digest)?;
+
+        // members are registered with a rate commitment binding their message limit,
+        // which stays private
+        let rate_commitment = rln_chip.hash(
+            layouter.namespace(|| "rate commitment"),
+            [commitment, user_message_limit]
+        )?;
 
         let chip = MerkleChip::construct(config.merkle_config.clone());
 
@@ -158,13 +217,15 @@ impl plonk::Circuit<pallas::Base> for Circuit {
         let root = 
         inclusion_proof.calculate_root(
             layouter.namespace(|| "merkle root"),
-            commitment
+            rate_commitment
         )?;
 
         self.expose_public(layouter.namespace(|| "expose y"), config.instance, y, Y)?;
         self.expose_public(layouter.namespace(|| "expose nullifier"), config.instance, nullifier, NULLIFIER)?;
         self.expose_public(layouter.namespace(|| "expose signal"), config.instance, signal, SIGNAL)?;
         self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, ROOT)?;
+        self.expose_public(layouter.namespace(|| "expose rln_identifier"), config.instance, rln_identifier, RLN_IDENTIFIER)?;
+        self.expose_public(layouter.namespace(|| "expose epoch"), config.instance, epoch, EPOCH)?;
         Ok(())
     }
 }
@@ -175,22 +236,21 @@ mod test {
         dev::MockProver,
         pasta::Fp,
     };
-    use super::{Circuit, DEPTH};
-    use crate::poseidon::{Hash, P128Pow5T3, ConstantLength};
+    use super::{Circuit, Y, NULLIFIER, RLN_IDENTIFIER, EPOCH};
     use crate::merkle::IncrementalTree;
     use rand;
-    use std::convert::TryInto;
     use ff::Field;
-    use crate::client::{calculate_output, retrieve_secret};
+    use crate::client::{calculate_output, rate_commitment, retrieve_secret};
+    use crate::error::Error;
 
-    #[test]
-    fn round_trip() {
+    fn circuit<const DEPTH: usize>(message_id: u64, user_message_limit: u64) -> (Circuit<DEPTH>, Vec<Fp>) {
         let mut rng = rand::thread_rng();
         let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
-        let k = 15;
 
         let secret = Fp::random(&mut rng);
-        let commitment = Hash::init(P128Pow5T3, ConstantLength::<1>).hash([secret]);
+        let message_id = Fp::from(message_id);
+        let user_message_limit = Fp::from(user_message_limit);
+        let commitment = rate_commitment(secret, user_message_limit);
 
         let num_of_leaves = 5;
 
@@ -200,42 +260,139 @@ mod test {
 
         tree.insert(commitment);
 
-        let (siblings, pos) = tree.witness(commitment);
-        let pos: Vec<Option<bool>> = pos.iter().map(|pos| Some(*pos)).collect();
-        let siblings: Vec<Option<Fp>> = siblings.iter().map(|sibling| Some(*sibling)).collect();
+        let (siblings, pos) = tree.proof(commitment).unwrap().circuit_witness::<DEPTH>().unwrap();
 
         let epoch = Fp::random(&mut rng);
+        let rln_identifier = Fp::random(&mut rng);
         let msg = "hello rln";
-        let (y, nullifier, signal) = calculate_output(secret, epoch, msg);
+        let (y, nullifier, signal) = calculate_output(secret, epoch, rln_identifier, message_id, msg);
     
-        let circuit = Circuit {
+        let circuit = Circuit::<DEPTH> {
             secret: Some(secret),
             signal: Some(signal),
-            siblings: siblings.clone().try_into().expect("siblings with incorrect length"),
-            pos: pos.clone().try_into().expect("pos with incorrect length"),
-            epoch: Some(epoch)
+            siblings,
+            pos,
+            epoch: Some(epoch),
+            rln_identifier: Some(rln_identifier),
+            message_id: Some(message_id),
+            user_message_limit: Some(user_message_limit)
         };
 
-        let public_inputs = vec![y, nullifier, signal, tree.root()];
+        let public_inputs = vec![y, nullifier, signal, tree.root(), rln_identifier, epoch];
+        (circuit, public_inputs)
+    }
+
+    fn round_trip<const DEPTH: usize>() {
+        let k = 15;
+
+        let (circuit, public_inputs) = circuit::<DEPTH>(2, 3);
         let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
     }
 
+    #[test]
+    fn round_trip_depth_16() {
+        round_trip::<16>();
+    }
+
+    #[test]
+    fn round_trip_depth_30() {
+        round_trip::<30>();
+    }
+
+    #[test]
+    fn epoch_mismatch() {
+        let k = 15;
+
+        let (circuit, mut public_inputs) = circuit::<16>(0, 1);
+        public_inputs[EPOCH] += Fp::one();
+        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn other_epoch() {
+        let k = 15;
+
+        // shares and nullifier of another epoch, with the public epoch unchanged
+        let (mut circuit, mut public_inputs) = circuit::<16>(0, 1);
+        let other_epoch = public_inputs[EPOCH] + Fp::one();
+        let (y, nullifier, _) = calculate_output(
+            circuit.secret.unwrap(),
+            other_epoch,
+            public_inputs[RLN_IDENTIFIER],
+            Fp::zero(),
+            "hello rln"
+        );
+        circuit.epoch = Some(other_epoch);
+        public_inputs[Y] = y;
+        public_inputs[NULLIFIER] = nullifier;
+        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn message_limit_exceeded() {
+        let k = 15;
+
+        let (circuit, public_inputs) = circuit::<16>(3, 3);
+        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn message_limit_not_registered() {
+        let k = 15;
+
+        // the limit is private, claiming a higher one than registered changes the root
+        let (mut circuit, public_inputs) = circuit::<16>(3, 3);
+        circuit.user_message_limit = Some(Fp::from(4));
+        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
     #[test]
     fn slash() {
         let mut rng = rand::thread_rng();
         let secret = Fp::random(&mut rng);
 
         let epoch = Fp::random(&mut rng);
+        let rln_identifier = Fp::random(&mut rng);
+        let message_id = Fp::from(0);
         let msg1 = "hello rln";
-        let (y1, nullifier1, x1) = calculate_output(secret, epoch, msg1);
+        let (y1, nullifier1, x1) = calculate_output(secret, epoch, rln_identifier, message_id, msg1);
 
         let msg2 = "hello again";
-        let (y2, nullifier2, x2) = calculate_output(secret, epoch, msg2);
+        let (y2, nullifier2, x2) = calculate_output(secret, epoch, rln_identifier, message_id, msg2);
 
         assert_eq!(nullifier1, nullifier2);
 
-        let retrieved_secret = retrieve_secret(x1, y1, x2, y2);
+        let retrieved_secret = retrieve_secret(x1, y1, x2, y2).unwrap();
         assert_eq!(secret, retrieved_secret);
+
+        // resending the same signal reveals nothing
+        assert!(matches!(retrieve_secret(x1, y1, x1, y1), Err(Error::IdenticalShares)));
+
+        // another message slot in the same epoch gets its own nullifier
+        let (_, nullifier3, _) = calculate_output(secret, epoch, rln_identifier, Fp::from(1), msg2);
+        assert_ne!(nullifier1, nullifier3);
+    }
+
+    #[test]
+    fn app_separation() {
+        let mut rng = rand::thread_rng();
+        let secret = Fp::random(&mut rng);
+
+        let epoch = Fp::random(&mut rng);
+        let message_id = Fp::from(0);
+        let app1 = Fp::from(1);
+        let app2 = Fp::from(2);
+
+        let (y1, nullifier1, x1) = calculate_output(secret, epoch, app1, message_id, "hello rln");
+        let (y2, nullifier2, x2) = calculate_output(secret, epoch, app2, message_id, "hello again");
+
+        // signals in different apps are unlinkable and do not leak the secret together
+        assert_ne!(nullifier1, nullifier2);
+        assert_ne!(secret, retrieve_secret(x1, y1, x2, y2).unwrap());
     }
 }
\ No newline at end of file
@@ -2,17 +2,210 @@ use crate::halo2::pasta::Fp;
 use crate::poseidon::{Hash, ConstantLength, P128Pow5T3};
 use crate::hash_to_field::hash_to_field;
 use ff::*; 
+use crate::error::Error;
+use crate::circuit::Circuit;
+use crate::merkle::{IncrementalTree, hasher::PoseidonHasher, store::NodeStore};
+use crate::keys::ProvingKey;
+use crate::proof::{Instance, Proof};
+use crate::transcript::{Blake2bTranscript, TranscriptScheme};
 
-pub fn calculate_output(secret: Fp, epoch: Fp, signal: &str) -> (Fp, Fp, Fp) {
+/// Identity commitment of a member, `Poseidon(secret)`.
+pub fn identity_commitment(secret: Fp) -> Fp {
+    Hash::init(P128Pow5T3, ConstantLength::<1>).hash([secret])
+}
+
+/// Leaf registered in the membership tree, binding the member's message limit per epoch.
+pub fn rate_commitment(secret: Fp, user_message_limit: Fp) -> Fp {
+    Hash::init(P128Pow5T3, ConstantLength::<2>).hash([identity_commitment(secret), user_message_limit])
+}
+
+/// External nullifier of an epoch within the application identified by `rln_identifier`.
+///
+/// Applications sharing one membership tree get separate nullifier spaces.
+pub fn external_nullifier(epoch: Fp, rln_identifier: Fp) -> Fp {
+    Hash::init(P128Pow5T3, ConstantLength::<2>).hash([epoch, rln_identifier])
+}
+
+/// Computes the share and nullifier for the `message_id`-th signal of an epoch.
+///
+/// `message_id` must be below the member's `user_message_limit` for the proof to verify.
+pub fn calculate_output(secret: Fp, epoch: Fp, rln_identifier: Fp, message_id: Fp, signal: &str) -> (Fp, Fp, Fp) {
     let signal = hash_to_field(signal.as_bytes());
-    let coef = Hash::init(P128Pow5T3, ConstantLength::<2>).hash([secret, epoch]);
+    let external_nullifier = external_nullifier(epoch, rln_identifier);
+    let epoch_key = Hash::init(P128Pow5T3, ConstantLength::<2>).hash([secret, external_nullifier]);
+    let coef = Hash::init(P128Pow5T3, ConstantLength::<2>).hash([epoch_key, message_id]);
     let y = coef * signal + secret;
     let nullifier = Hash::init(P128Pow5T3, ConstantLength::<1>).hash([coef]);
 
     (y, nullifier, signal)
 }
 
-pub fn retrieve_secret(x1: Fp, y1: Fp, x2: Fp, y2:Fp) -> Fp {
-    let slope = (y2 - y1) * (x2 - x1).invert().unwrap();
-    y1 - slope * x1
-}
\ No newline at end of file
+/// Recovers the secret from two shares of the same epoch and message.
+///
+/// Fails with [`Error::IdenticalShares`] when both shares were computed for the same signal.
+pub fn retrieve_secret(x1: Fp, y1: Fp, x2: Fp, y2:Fp) -> Result<Fp, Error> {
+    let inv = Option::<Fp>::from((x2 - x1).invert()).ok_or(Error::IdenticalShares)?;
+    let slope = (y2 - y1) * inv;
+    Ok(y1 - slope * x1)
+}
+
+/// A signal to prove for a member of the tree.
+#[derive(Clone, Debug)]
+pub struct SignalRequest {
+    pub secret: Fp,
+    pub user_message_limit: Fp,
+    pub message_id: Fp,
+    pub epoch: Fp,
+    pub rln_identifier: Fp,
+    pub signal: String,
+}
+
+impl SignalRequest {
+    /// Builds the circuit witness and public inputs of the signal against the current root of `tree`.
+    ///
+    /// Fails with [`Error::UnknownLeaf`] if the member's rate commitment is not in the tree.
+    pub fn witness<const DEPTH: usize, S: NodeStore<Fp>>(
+        &self,
+        tree: &IncrementalTree<PoseidonHasher, S>,
+    ) -> Result<(Circuit<DEPTH>, Instance), Error> {
+        let commitment = rate_commitment(self.secret, self.user_message_limit);
+        let (siblings, pos) = tree.proof(commitment)?.circuit_witness::<DEPTH>()?;
+        let (y, nullifier, signal) =
+            calculate_output(self.secret, self.epoch, self.rln_identifier, self.message_id, &self.signal);
+
+        let circuit = Circuit {
+            secret: Some(self.secret),
+            signal: Some(signal),
+            siblings,
+            pos,
+            epoch: Some(self.epoch),
+            rln_identifier: Some(self.rln_identifier),
+            message_id: Some(self.message_id),
+            user_message_limit: Some(self.user_message_limit)
+        };
+        let instance = Instance {
+            y,
+            nullifier,
+            signal,
+            root: tree.root(),
+            rln_identifier: self.rln_identifier,
+            epoch: self.epoch
+        };
+
+        Ok((circuit, instance))
+    }
+}
+
+/// Builds the witness of every request against the current root of `tree` and proves
+/// them all in one proof made with the transcript `T`, returning it with the instances
+/// in the order of `requests`.
+pub fn prove_signals<const DEPTH: usize, T: TranscriptScheme, S: NodeStore<Fp>>(
+    pk: &ProvingKey<DEPTH>,
+    tree: &IncrementalTree<PoseidonHasher, S>,
+    requests: &[SignalRequest],
+) -> Result<(Proof<DEPTH, T>, Vec<Instance>), Error> {
+    if requests.is_empty() {
+        return Err(Error::InvalidPublicInputs);
+    }
+
+    let (circuits, instances): (Vec<_>, Vec<_>) = requests
+        .iter()
+        .map(|request| request.witness::<DEPTH, S>(tree))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+
+    let proof = Proof::prove(pk, &circuits, &instances)?;
+    Ok((proof, instances))
+}
+
+/// Proves every request in one proof with the default transcript, see [`prove_signals`].
+pub fn create_signals<const DEPTH: usize, S: NodeStore<Fp>>(
+    pk: &ProvingKey<DEPTH>,
+    tree: &IncrementalTree<PoseidonHasher, S>,
+    requests: &[SignalRequest],
+) -> Result<(Proof<DEPTH>, Vec<Instance>), Error> {
+    prove_signals::<DEPTH, Blake2bTranscript, S>(pk, tree, requests)
+}
+
+#[cfg(test)]
+mod test {
+    use ff::Field;
+    use crate::halo2::pasta::Fp;
+
+    use crate::keys::{ProvingKey, VerifyingKey};
+    use crate::merkle::IncrementalTree;
+    use crate::proof::Instance;
+    use crate::error::Error;
+
+    use super::{create_signals, rate_commitment, SignalRequest};
+
+    const K: u32 = 11;
+    const DEPTH: usize = 16;
+
+    #[test]
+    fn multiple_signals() {
+        let mut rng = rand::thread_rng();
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let vk = VerifyingKey::<DEPTH>::build(K);
+
+        let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
+        let rln_identifier = Fp::random(&mut rng);
+        let requests: Vec<_> = ["first", "second", "third"]
+            .iter()
+            .map(|signal| {
+                let request = SignalRequest {
+                    secret: Fp::random(&mut rng),
+                    user_message_limit: Fp::from(1),
+                    message_id: Fp::from(0),
+                    epoch: Fp::random(&mut rng),
+                    rln_identifier,
+                    signal: signal.to_string(),
+                };
+                tree.insert(rate_commitment(request.secret, request.user_message_limit));
+                request
+            })
+            .collect();
+
+        let (proof, instances) = create_signals(&pk, &tree, &requests).unwrap();
+        assert_eq!(instances.len(), 3);
+        assert!(instances.iter().all(|instance| instance.root == tree.root()));
+        assert!(proof.verify(&vk, &instances, &tree).is_ok());
+
+        // the proof is bound to every public input of every instance
+        for i in 0..instances.len() {
+            let tamperings: [fn(&mut Instance); 5] = [
+                |instance| instance.y += Fp::one(),
+                |instance| instance.nullifier += Fp::one(),
+                |instance| instance.signal += Fp::one(),
+                |instance| instance.rln_identifier += Fp::one(),
+                |instance| instance.epoch += Fp::one(),
+            ];
+            for tamper in tamperings.iter() {
+                let mut tampered = instances.clone();
+                tamper(&mut tampered[i]);
+                assert!(matches!(proof.verify(&vk, &tampered, &tree), Err(Error::VerificationFailed)));
+            }
+
+            // a root the policy does not know is refused before verifying
+            let mut tampered = instances.clone();
+            tampered[i].root += Fp::one();
+            assert!(matches!(proof.verify(&vk, &tampered, &tree), Err(Error::UnknownRoot)));
+
+            // an older root of the tree is accepted by the policy but not by the proof
+            let history = tree.root_history();
+            let (older_root, _) = history.iter().nth(history.len() - 2).unwrap();
+            let mut tampered = instances.clone();
+            tampered[i].root = *older_root;
+            assert!(matches!(proof.verify(&vk, &tampered, &tree), Err(Error::VerificationFailed)));
+        }
+        let mut swapped = instances.clone();
+        swapped.swap(0, 2);
+        assert!(matches!(proof.verify(&vk, &swapped, &tree), Err(Error::VerificationFailed)));
+        assert!(proof.verify(&vk, &instances[..2], &tree).is_err());
+
+        let stranger = SignalRequest { secret: Fp::random(&mut rng), ..requests[0].clone() };
+        assert!(matches!(create_signals(&pk, &tree, &[requests[0].clone(), stranger]), Err(Error::UnknownLeaf)));
+        assert!(matches!(create_signals(&pk, &tree, &[]), Err(Error::InvalidPublicInputs)));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::halo2::plonk;
+use std::{fmt, io};
+
+/// Errors returned by the prover, the verifier, key (de)serialization, the
+/// membership tree and the client helpers.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing serialized data failed.
+    Io(io::Error),
+    /// A serialized key does not match the compiled circuit or is not a key at all.
+    InvalidKey(&'static str),
+    /// halo2 failed while building keys or creating a proof.
+    Plonk(plonk::Error),
+    /// The proof bytes could not be decoded.
+    MalformedProof,
+    /// A proof bundle cannot be decoded or was made for another circuit or key.
+    InvalidBundle(&'static str),
+    /// The proof was made against a root the verifier does not accept.
+    UnknownRoot,
+    /// The public inputs do not have the shape expected by the circuit.
+    InvalidPublicInputs,
+    /// The proof does not verify for the given public inputs.
+    ///
+    /// A proof checked against a public input it was not made for, such as
+    /// another epoch, fails here: the proof system cannot tell which input is wrong.
+    VerificationFailed,
+    /// The tree depth is above [`crate::merkle::MAX_DEPTH`].
+    DepthTooLarge(usize),
+    /// The zero value marks empty slots and cannot be inserted as a leaf.
+    ZeroLeaf,
+    /// Every slot of the tree is taken.
+    TreeFull,
+    /// The leaf is not in the membership tree.
+    UnknownLeaf,
+    /// No leaf has been inserted at this index.
+    IndexOutOfBounds(usize),
+    /// An encoded Merkle path cannot be decoded or does not have the expected depth.
+    InvalidMerkleProof(&'static str),
+    /// The key does not fit in the path bits of a sparse tree.
+    KeyOutOfRange,
+    /// Stored tree nodes are unreadable or do not match the stored root.
+    CorruptStore(&'static str),
+    /// A membership event is not the next one; holds the leaf index expected next.
+    EventGap(usize),
+    /// A membership event conflicts with the synced tree; holds its position in the event log.
+    Reorg(usize),
+    /// A line of a membership event log cannot be parsed; holds its line number.
+    InvalidEvent(usize),
+    /// Both shares were computed for the same signal, so the secret cannot be recovered.
+    IdenticalShares,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {}", err),
+            Error::InvalidKey(msg) => write!(f, "invalid key: {}", msg),
+            Error::Plonk(err) => write!(f, "proof system error: {:?}", err),
+            Error::MalformedProof => write!(f, "malformed proof encoding"),
+            Error::InvalidBundle(msg) => write!(f, "invalid proof bundle: {}", msg),
+            Error::UnknownRoot => write!(f, "proof is for an unknown root"),
+            Error::InvalidPublicInputs => write!(f, "invalid public inputs"),
+            Error::VerificationFailed => write!(f, "proof verification failed"),
+            Error::DepthTooLarge(depth) => write!(f, "tree depth {} is above the maximum", depth),
+            Error::ZeroLeaf => write!(f, "leaf cannot be equal to the zero value"),
+            Error::TreeFull => write!(f, "tree is full"),
+            Error::UnknownLeaf => write!(f, "leaf is not in the tree"),
+            Error::IndexOutOfBounds(index) => write!(f, "no leaf at index {}", index),
+            Error::InvalidMerkleProof(msg) => write!(f, "invalid merkle proof: {}", msg),
+            Error::KeyOutOfRange => write!(f, "key is out of range"),
+            Error::CorruptStore(msg) => write!(f, "corrupt tree store: {}", msg),
+            Error::EventGap(index) => write!(f, "expected the membership event of leaf {}", index),
+            Error::Reorg(position) => write!(f, "membership event {} conflicts with the synced tree", position),
+            Error::InvalidEvent(line) => write!(f, "invalid membership event on line {}", line),
+            Error::IdenticalShares => write!(f, "shares have the same signal"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<plonk::Error> for Error {
+    fn from(err: plonk::Error) -> Self {
+        Error::Plonk(err)
+    }
+}
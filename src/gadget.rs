@@ -1,5 +1,9 @@
 pub mod merkle;
+pub mod sparse_merkle;
 pub mod swap;
+pub mod select;
+pub mod quaternary;
+pub mod range;
 pub mod poseidon;
 pub mod rln;
 
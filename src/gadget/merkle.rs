@@ -86,6 +86,8 @@ mod test {
     use crate::gadget::poseidon::{Pow5T3Chip as PoseidonChip};
 
     use crate::merkle::IncrementalTree;
+    use crate::merkle::hasher::PoseidonHasher;
+    use crate::merkle::store::MemoryStore;
 
 
     #[derive(Clone, Debug)]
@@ -183,6 +185,8 @@ mod test {
                     leaf
                 )?;
 
+            self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, 0)?;
+
             Ok({})
         }
     }
@@ -192,7 +196,8 @@ mod test {
         let k = 9;
         let depth = 10;
 
-        let mut tree = IncrementalTree::new(Fp::zero(), depth);
+        // the tree is hashed like the gadget, so the gadget accepts its root
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(MemoryStore::new(), Fp::zero(), depth).unwrap();
 
         tree.insert(Fp::from(2));
         tree.insert(Fp::from(3));
@@ -202,21 +207,23 @@ mod test {
         tree.insert(Fp::from(7));
 
         let leaf = Fp::from(6);
-        let (siblings, pos) = tree.witness(leaf);
-
-        let pos: Vec<Option<bool>> = pos.iter().map(|pos| Some(*pos)).collect();
-        let siblings: Vec<Option<Fp>> = siblings.iter().map(|sibling| Some(*sibling)).collect();
-
+        let proof = tree.proof(leaf).unwrap();
+        assert!(proof.verify(tree.root(), leaf));
+        let (siblings, leaf_pos) = proof.circuit_witness::<10>().unwrap();
 
         let circuit = Circuit {
             leaf: Some(leaf),
-            siblings: siblings.try_into().expect("siblings with incorrect length"),
+            siblings,
             root: Some(tree.root()), 
-            leaf_pos: pos.try_into().expect("siblings with incorrect length")
+            leaf_pos
         };
 
-        let public_inputs = vec![];
+        let public_inputs = vec![tree.root()];
         let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
+
+        let wrong_root = vec![tree.root() + Fp::one()];
+        let prover = MockProver::run(k, &circuit, vec![wrong_root]).unwrap();
+        assert!(prover.verify().is_err());
     }
 }
\ No newline at end of file
@@ -7,7 +7,7 @@ use crate::halo2::{
 use pasta_curves::pallas;
 
 use std::{marker::PhantomData};
-use crate::utils::{NumericCell, UtilitiesInstructions, Numeric, CellValue, Var, from_cell_vale_to_numeric};
+use crate::utils::{NumericCell, UtilitiesInstructions, Numeric, CellValue, from_cell_vale_to_numeric};
 use crate::gadget::swap::{SwapConfig, SwapChip, SwapInstruction};
 use crate::gadget::poseidon::{HashInstruction, Pow5T3Config as PoseidonConfig, Pow5T3Chip as PoseidonChip, Hash};
 use crate::poseidon::{P128Pow5T3, ConstantLength};
@@ -82,6 +82,17 @@ impl<F: FieldExt> SwapInstruction<F> for MerkleChip<F> {
         let chip = SwapChip::<F>::construct(config);
         chip.swap(layouter, pair, swap)
     }
+
+    fn swap_with_bit(
+        &self,
+        layouter: impl Layouter<F>,
+        pair: (Self::Var, Option<F>),
+        bit: Self::Var,
+    ) -> Result<(Self::Var, Self::Var), Error> {
+        let config = self.config().swap_config.clone();
+        let chip = SwapChip::<F>::construct(config);
+        chip.swap_with_bit(layouter, pair, bit)
+    }
 }
 
 impl<const LEN: usize> HashInstruction<pallas::Base, LEN> for MerkleChip<pallas::Base> {
@@ -105,7 +116,7 @@ impl<const LEN: usize> HashInstruction<pallas::Base, LEN> for MerkleChip<pallas:
         >  = Hash::init(chip, layouter.namespace(|| "init hasher"), ConstantLength::<LEN>)?;
         let word = poseidon_hasher.hash(layouter.namespace(|| "digest message"), message)?;
         let digest: CellValue<pallas::Base> = word.inner().into();
-        let assigned = from_cell_vale_to_numeric(layouter.namespace(|| "dummy"), config.advice[0], digest.value())?;
+        let assigned = from_cell_vale_to_numeric(layouter.namespace(|| "dummy"), config.advice[0], digest)?;
         Ok(assigned)
     }
 }
@@ -122,6 +133,8 @@ impl MerkleInstructions for MerkleChip<pallas::Base> {
         let config = self.config.clone();
         let hashed = self.hash(layouter.namespace(|| format!("hashing: {}", level)), [left, right])?;
 
+        let value = hashed.value();
+
         layouter.assign_region(
             || "witness root",
             |mut region| {
@@ -130,8 +143,9 @@ impl MerkleInstructions for MerkleChip<pallas::Base> {
                     || "root",
                     config.advice[level % 3],
                     row_offset,
-                    || hashed.value().ok_or(Error::Synthesis),
+                    || value.ok_or(Error::Synthesis),
                 )?;
+                region.constrain_equal(hashed.cell(), cell.cell())?;
 
                 Ok(NumericCell::new(cell))
             }
@@ -9,8 +9,11 @@ use crate::halo2::{
     plonk::Error,
 };
 
+mod pow5;
+pub use pow5::{Pow5Chip, Pow5Config, StateWord};
+
 mod pow5t3;
-pub use pow5t3::{Pow5T3Chip, Pow5T3Config, StateWord};
+pub use pow5t3::{Pow5T3Chip, Pow5T3Config};
 
 use crate::poseidon::{ConstantLength, Domain, Spec, Sponge, SpongeState, State};
 use crate::utils::{CellValue, NumericCell, UtilitiesInstructions};
@@ -0,0 +1,677 @@
+use std::convert::TryInto;
+use std::iter;
+
+use crate::halo2::{
+    arithmetic::FieldExt,
+    circuit::{Cell, Chip, Layouter, Region},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, Selector},
+    poly::Rotation,
+};
+
+use super::{PoseidonDuplexInstructions, PoseidonInstructions};
+use crate::poseidon::{Domain, Mds, Spec, SpongeState, State};
+use crate::utils::{CellValue, Var};
+
+/// Configuration for a [`Pow5Chip`].
+#[derive(Clone, Debug)]
+pub struct Pow5Config<F: FieldExt, const WIDTH: usize, const RATE: usize> {
+    state: [Column<Advice>; WIDTH],
+    partial_sbox: Column<Advice>,
+    rc_a: [Column<Fixed>; WIDTH],
+    rc_b: [Column<Fixed>; WIDTH],
+    s_full: Selector,
+    s_partial: Selector,
+    s_pad_and_add: Selector,
+
+    half_full_rounds: usize,
+    half_partial_rounds: usize,
+    alpha: [u64; 4],
+    round_constants: Vec<[F; WIDTH]>,
+    m_reg: Mds<F, WIDTH>,
+    m_inv: Mds<F, WIDTH>,
+}
+
+impl<F: FieldExt, const WIDTH: usize, const RATE: usize> Pow5Config<F, WIDTH, RATE> {
+    pub fn state(&self) -> [Column<Advice>; WIDTH] {
+        self.state
+    }
+}
+
+/// A Poseidon chip using an $x^5$ S-Box, with any width and a single capacity element.
+///
+/// [`super::Pow5T3Chip`] is this chip with a width of 3, wider sponges such as
+/// [`crate::poseidon::P128Pow5T5`] use the same gates.
+#[derive(Debug)]
+pub struct Pow5Chip<F: FieldExt, const WIDTH: usize, const RATE: usize> {
+    config: Pow5Config<F, WIDTH, RATE>,
+}
+
+impl<F: FieldExt, const WIDTH: usize, const RATE: usize> Pow5Chip<F, WIDTH, RATE> {
+    /// Configures this chip for use in a circuit.
+    ///
+    /// # Side-effects
+    ///
+    /// All columns in `state` will be equality-enabled.
+    pub fn configure<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        spec: S,
+        state: [Column<Advice>; WIDTH],
+        partial_sbox: Column<Advice>,
+        rc_a: [Column<Fixed>; WIDTH],
+        rc_b: [Column<Fixed>; WIDTH],
+    ) -> Pow5Config<F, WIDTH, RATE> {
+        assert_eq!(RATE + 1, WIDTH);
+
+        // Generate constants for the Poseidon permutation.
+        // This gadget requires R_F and R_P to be even.
+        assert!(S::full_rounds() & 1 == 0);
+        assert!(S::partial_rounds() & 1 == 0);
+        let half_full_rounds = S::full_rounds() / 2;
+        let half_partial_rounds = S::partial_rounds() / 2;
+        let (round_constants, m_reg, m_inv) = spec.constants();
+
+        // This allows state words to be initialized (by constraining them equal to fixed
+        // values), and used in a permutation from an arbitrary region. rc_a is used in
+        // every permutation round, while rc_b is empty in the initial and final full
+        // rounds, so we use rc_b as "scratch space" for fixed values (enabling potential
+        // layouter optimisations).
+        for column in iter::empty()
+            .chain(state.iter().cloned().map(|c| c.into()))
+            .chain(rc_b.iter().cloned().map(|c| c.into()))
+        {
+            meta.enable_equality(column);
+        }
+
+        let s_full = meta.selector();
+        let s_partial = meta.selector();
+        let s_pad_and_add = meta.selector();
+
+        let alpha = [5, 0, 0, 0];
+        let pow_5 = |v: Expression<F>| {
+            let v2 = v.clone() * v.clone();
+            v2.clone() * v2 * v
+        };
+
+        meta.create_gate("full round", |meta| {
+            let s_full = meta.query_selector(s_full);
+
+            (0..WIDTH)
+                .map(|next_idx| {
+                    let state_next = meta.query_advice(state[next_idx], Rotation::next());
+                    let expr = (0..WIDTH)
+                        .map(|idx| {
+                            let state_cur = meta.query_advice(state[idx], Rotation::cur());
+                            let rc_a = meta.query_fixed(rc_a[idx], Rotation::cur());
+                            pow_5(state_cur + rc_a) * m_reg[next_idx][idx]
+                        })
+                        .reduce(|acc, term| acc + term)
+                        .expect("WIDTH > 0");
+                    s_full.clone() * (expr - state_next)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("partial rounds", |meta| {
+            let cur_0 = meta.query_advice(state[0], Rotation::cur());
+            let mid_0 = meta.query_advice(partial_sbox, Rotation::cur());
+
+            let rc_a0 = meta.query_fixed(rc_a[0], Rotation::cur());
+            let rc_b0 = meta.query_fixed(rc_b[0], Rotation::cur());
+
+            let s_partial = meta.query_selector(s_partial);
+
+            // the state after the first half round, multiplied by row `idx` of the MDS
+            let mid = |idx: usize, meta: &mut crate::halo2::plonk::VirtualCells<F>| {
+                let mid = mid_0.clone() * m_reg[idx][0];
+                (1..WIDTH).fold(mid, |acc, cur_idx| {
+                    let cur = meta.query_advice(state[cur_idx], Rotation::cur());
+                    let rc_a = meta.query_fixed(rc_a[cur_idx], Rotation::cur());
+                    acc + (cur + rc_a) * m_reg[idx][cur_idx]
+                })
+            };
+
+            // the next state, multiplied by row `idx` of the inverse MDS
+            let next = |idx: usize, meta: &mut crate::halo2::plonk::VirtualCells<F>| {
+                (0..WIDTH)
+                    .map(|next_idx| {
+                        let next = meta.query_advice(state[next_idx], Rotation::next());
+                        next * m_inv[idx][next_idx]
+                    })
+                    .reduce(|acc, next| acc + next)
+                    .expect("WIDTH > 0")
+            };
+
+            let partial_round_linear = |idx: usize, meta: &mut crate::halo2::plonk::VirtualCells<F>| {
+                let rc_b = meta.query_fixed(rc_b[idx], Rotation::cur());
+                mid(idx, meta) + rc_b - next(idx, meta)
+            };
+
+            iter::empty()
+                // state[0] round a
+                .chain(Some(pow_5(cur_0 + rc_a0) - mid_0.clone()))
+                // state[0] round b
+                .chain(Some(pow_5(mid(0, meta) + rc_b0) - next(0, meta)))
+                .chain((1..WIDTH).map(|idx| partial_round_linear(idx, meta)))
+                .map(|poly| s_partial.clone() * poly)
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("pad-and-add", |meta| {
+            let s_pad_and_add = meta.query_selector(s_pad_and_add);
+
+            (0..WIDTH)
+                .map(|idx| {
+                    let initial_state = meta.query_advice(state[idx], Rotation::prev());
+                    let output_state = meta.query_advice(state[idx], Rotation::next());
+
+                    // We pad the input by storing the required padding in fixed columns and
+                    // then constraining the corresponding input columns to be equal to it.
+                    if idx < RATE {
+                        let input = meta.query_advice(state[idx], Rotation::cur());
+                        s_pad_and_add.clone() * (initial_state + input - output_state)
+                    } else {
+                        // The capacity element is never altered by the input.
+                        s_pad_and_add.clone() * (initial_state - output_state)
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Pow5Config {
+            state,
+            partial_sbox,
+            rc_a,
+            rc_b,
+            s_full,
+            s_partial,
+            s_pad_and_add,
+            half_full_rounds,
+            half_partial_rounds,
+            alpha,
+            round_constants,
+            m_reg,
+            m_inv,
+        }
+    }
+
+    pub fn construct(config: Pow5Config<F, WIDTH, RATE>) -> Self {
+        Pow5Chip { config }
+    }
+}
+
+impl<F: FieldExt, const WIDTH: usize, const RATE: usize> Chip<F> for Pow5Chip<F, WIDTH, RATE> {
+    type Config = Pow5Config<F, WIDTH, RATE>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, S: Spec<F, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>
+    PoseidonInstructions<F, S, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE>
+{
+    type Word = StateWord<F>;
+
+    fn permute(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<Self::Word, WIDTH>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "permute state",
+            |mut region| {
+                // Load the initial state into this region.
+                let state = Pow5State::load(&mut region, config, initial_state)?;
+
+                let state = (0..config.half_full_rounds).fold(Ok(state), |res, r| {
+                    res.and_then(|state| state.full_round(&mut region, config, r, r))
+                })?;
+
+                let state = (0..config.half_partial_rounds).fold(Ok(state), |res, r| {
+                    res.and_then(|state| {
+                        state.partial_round(
+                            &mut region,
+                            config,
+                            config.half_full_rounds + 2 * r,
+                            config.half_full_rounds + r,
+                        )
+                    })
+                })?;
+
+                let state = (0..config.half_full_rounds).fold(Ok(state), |res, r| {
+                    res.and_then(|state| {
+                        state.full_round(
+                            &mut region,
+                            config,
+                            config.half_full_rounds + 2 * config.half_partial_rounds + r,
+                            config.half_full_rounds + config.half_partial_rounds + r,
+                        )
+                    })
+                })?;
+
+                Ok(state.0)
+            },
+        )
+    }
+}
+
+impl<F: FieldExt, S: Spec<F, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>
+    PoseidonDuplexInstructions<F, S, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE>
+{
+    fn initial_state(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        domain: &impl Domain<F, WIDTH, RATE>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || format!("initial state for domain {:?}", domain),
+            |mut region| {
+                let mut load_state_word = |i: usize, value: F| -> Result<StateWord<F>, Error> {
+                    let var = region.assign_advice_from_constant(
+                        || format!("state_{}", i),
+                        config.state[i],
+                        0,
+                        value,
+                    )?;
+                    Ok(StateWord::new(var.cell(), Some(value)))
+                };
+
+                let state = (0..WIDTH)
+                    .map(|i| {
+                        let value = if i < RATE { F::zero() } else { domain.initial_capacity_element() };
+                        load_state_word(i, value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(state.try_into().unwrap())
+            },
+        )
+    }
+
+    fn pad_and_add(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        domain: &impl Domain<F, WIDTH, RATE>,
+        initial_state: &State<Self::Word, WIDTH>,
+        input: &SpongeState<Self::Word, RATE>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || format!("pad-and-add for domain {:?}", domain),
+            |mut region| {
+                config.s_pad_and_add.enable(&mut region, 1)?;
+
+                // Load the initial state into this region.
+                let initial_state = load_state(&mut region, config, initial_state)?;
+
+                let padding_values = domain.padding();
+
+                // Load the input and padding into this region.
+                let mut load_input_word = |i: usize| -> Result<StateWord<F>, Error> {
+                    let (constraint_var, value) = match (input[i], padding_values[i]) {
+                        (Some(word), None) => (word.var, word.value),
+                        (None, Some(padding_value)) => {
+                            let padding_var = region.assign_fixed(
+                                || format!("load pad_{}", i),
+                                config.rc_b[i],
+                                1,
+                                || Ok(padding_value),
+                            )?;
+                            (padding_var.cell(), Some(padding_value))
+                        }
+                        _ => panic!("Input and padding don't match"),
+                    };
+                    let var = region.assign_advice(
+                        || format!("load input_{}", i),
+                        config.state[i],
+                        1,
+                        || value.ok_or(Error::Synthesis),
+                    )?;
+                    region.constrain_equal(constraint_var, var.cell())?;
+
+                    Ok(StateWord::new(var.cell(), value))
+                };
+                let input = (0..RATE)
+                    .map(&mut load_input_word)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // Constrain the output.
+                let mut constrain_output_word = |i: usize| -> Result<StateWord<F>, Error> {
+                    let value = initial_state[i].value.and_then(|initial_word| {
+                        input
+                            .get(i)
+                            .map(|word| word.value)
+                            // The capacity element is never altered by the input.
+                            .unwrap_or_else(|| Some(F::zero()))
+                            .map(|input_word| initial_word + input_word)
+                    });
+                    let var = region.assign_advice(
+                        || format!("load output_{}", i),
+                        config.state[i],
+                        2,
+                        || value.ok_or(Error::Synthesis),
+                    )?;
+                    Ok(StateWord::new(var.cell(), value))
+                };
+
+                let output = (0..WIDTH)
+                    .map(&mut constrain_output_word)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(output.try_into().unwrap())
+            },
+        )
+    }
+
+    fn get_output(state: &State<Self::Word, WIDTH>) -> SpongeState<Self::Word, RATE> {
+        let mut output = [None; RATE];
+        for (word, value) in output.iter_mut().zip(state.iter()) {
+            *word = Some(*value);
+        }
+        output
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StateWord<F: FieldExt> {
+    pub(super) var: Cell,
+    pub(super) value: Option<F>,
+}
+
+impl<F: FieldExt> StateWord<F> {
+    pub fn new(var: Cell, value: Option<F>) -> Self {
+        Self { var, value }
+    }
+}
+
+impl<F: FieldExt> From<StateWord<F>> for CellValue<F> {
+    fn from(state_word: StateWord<F>) -> CellValue<F> {
+        CellValue::new(state_word.var, state_word.value)
+    }
+}
+
+impl<F: FieldExt> From<CellValue<F>> for StateWord<F> {
+    fn from(cell_value: CellValue<F>) -> StateWord<F> {
+        StateWord::new(cell_value.cell(), cell_value.value())
+    }
+}
+
+/// Copies `initial_state` into the first row of the region.
+fn load_state<F: FieldExt, const WIDTH: usize, const RATE: usize>(
+    region: &mut Region<F>,
+    config: &Pow5Config<F, WIDTH, RATE>,
+    initial_state: &State<StateWord<F>, WIDTH>,
+) -> Result<[StateWord<F>; WIDTH], Error> {
+    let state = (0..WIDTH)
+        .map(|i| {
+            let value = initial_state[i].value;
+            let var = region.assign_advice(
+                || format!("load state_{}", i),
+                config.state[i],
+                0,
+                || value.ok_or(Error::Synthesis),
+            )?;
+            region.constrain_equal(initial_state[i].var, var.cell())?;
+            Ok(StateWord::new(var.cell(), value))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(state.try_into().unwrap())
+}
+
+#[derive(Debug)]
+struct Pow5State<F: FieldExt, const WIDTH: usize>([StateWord<F>; WIDTH]);
+
+impl<F: FieldExt, const WIDTH: usize> Pow5State<F, WIDTH> {
+    fn full_round<const RATE: usize>(
+        self,
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE>,
+        round: usize,
+        offset: usize,
+    ) -> Result<Self, Error> {
+        Self::round(region, config, round, offset, config.s_full, |_| {
+            let r = self.values().map(|q| {
+                let mut r = [F::zero(); WIDTH];
+                for i in 0..WIDTH {
+                    r[i] = (q[i] + config.round_constants[round][i]).pow(&config.alpha);
+                }
+                r
+            });
+
+            Ok((round + 1, mds_mul(&config.m_reg, r)))
+        })
+    }
+
+    fn partial_round<const RATE: usize>(
+        self,
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE>,
+        round: usize,
+        offset: usize,
+    ) -> Result<Self, Error> {
+        Self::round(region, config, round, offset, config.s_partial, |region| {
+            let m = &config.m_reg;
+
+            let r = self.values().map(|p| {
+                let mut r = [F::zero(); WIDTH];
+                for i in 0..WIDTH {
+                    r[i] = p[i] + config.round_constants[round][i];
+                }
+                r[0] = r[0].pow(&config.alpha);
+                r
+            });
+
+            region.assign_advice(
+                || format!("round_{} partial_sbox", round),
+                config.partial_sbox,
+                offset,
+                || r.map(|r| r[0]).ok_or(Error::Synthesis),
+            )?;
+
+            let p_mid = mds_mul(m, r);
+
+            // Load the second round constants.
+            for i in 0..WIDTH {
+                region.assign_fixed(
+                    || format!("round_{} rc_{}", round + 1, i),
+                    config.rc_b[i],
+                    offset,
+                    || Ok(config.round_constants[round + 1][i]),
+                )?;
+            }
+
+            let r_mid = p_mid.iter().all(|p| p.is_some()).then(|| {
+                let mut r = [F::zero(); WIDTH];
+                for i in 0..WIDTH {
+                    r[i] = p_mid[i].unwrap() + config.round_constants[round + 1][i];
+                }
+                r[0] = r[0].pow(&config.alpha);
+                r
+            });
+
+            Ok((round + 2, mds_mul(m, r_mid)))
+        })
+    }
+
+    fn load<const RATE: usize>(
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+    ) -> Result<Self, Error> {
+        load_state(region, config, initial_state).map(Pow5State)
+    }
+
+    fn values(&self) -> Option<[F; WIDTH]> {
+        let mut values = [F::zero(); WIDTH];
+        for (value, word) in values.iter_mut().zip(self.0.iter()) {
+            *value = word.value?;
+        }
+        Some(values)
+    }
+
+    fn round<const RATE: usize>(
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE>,
+        round: usize,
+        offset: usize,
+        round_gate: Selector,
+        round_fn: impl FnOnce(&mut Region<F>) -> Result<(usize, [Option<F>; WIDTH]), Error>,
+    ) -> Result<Self, Error> {
+        // Enable the required gate.
+        round_gate.enable(region, offset)?;
+
+        // Load the round constants.
+        for i in 0..WIDTH {
+            region.assign_fixed(
+                || format!("round_{} rc_{}", round, i),
+                config.rc_a[i],
+                offset,
+                || Ok(config.round_constants[round][i]),
+            )?;
+        }
+
+        // Compute the next round's state.
+        let (next_round, next_state) = round_fn(region)?;
+
+        let next_state = (0..WIDTH)
+            .map(|i| {
+                let value = next_state[i];
+                let var = region.assign_advice(
+                    || format!("round_{} state_{}", next_round, i),
+                    config.state[i],
+                    offset + 1,
+                    || value.ok_or(Error::Synthesis),
+                )?;
+                Ok(StateWord::new(var.cell(), value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Pow5State(next_state.try_into().unwrap()))
+    }
+}
+
+/// Multiplies `state` by the MDS matrix `m`.
+fn mds_mul<F: FieldExt, const WIDTH: usize>(
+    m: &Mds<F, WIDTH>,
+    state: Option<[F; WIDTH]>,
+) -> [Option<F>; WIDTH] {
+    let mut next = [None; WIDTH];
+    if let Some(state) = state {
+        for (i, next) in next.iter_mut().enumerate() {
+            *next = Some((0..WIDTH).fold(F::zero(), |acc, j| acc + m[i][j] * state[j]));
+        }
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::halo2::{
+        circuit::{Layouter, SimpleFloorPlanner},
+        dev::MockProver,
+        pasta::Fp,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
+
+    use super::{PoseidonInstructions, Pow5Chip, Pow5Config, StateWord};
+    use crate::poseidon::{self, P128Pow5T5, Spec};
+
+    const WIDTH: usize = 5;
+    const RATE: usize = 4;
+
+    struct PermuteCircuit {}
+
+    impl Circuit<Fp> for PermuteCircuit {
+        type Config = Pow5Config<Fp, WIDTH, RATE>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            PermuteCircuit {}
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, WIDTH, RATE> {
+            let state = [(); WIDTH].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+
+            let rc_a = [(); WIDTH].map(|_| meta.fixed_column());
+            let rc_b = [(); WIDTH].map(|_| meta.fixed_column());
+
+            Pow5Chip::configure(meta, P128Pow5T5, state, partial_sbox, rc_a, rc_b)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, WIDTH, RATE>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let state = (0..WIDTH)
+                        .map(|i| {
+                            let value = Some(Fp::from(i as u64));
+                            let var = region.assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || value.ok_or(Error::Synthesis),
+                            )?;
+                            Ok(StateWord::new(var.cell(), value))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    Ok(state.try_into().unwrap())
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.clone());
+            let final_state = <Pow5Chip<_, WIDTH, RATE> as PoseidonInstructions<
+                Fp,
+                P128Pow5T5,
+                WIDTH,
+                RATE,
+            >>::permute(&chip, &mut layouter, &initial_state)?;
+
+            // For the purpose of this test, compute the real final state inline.
+            let mut expected_final_state = [0, 1, 2, 3, 4].map(|i| Fp::from(i as u64));
+            let (round_constants, mds, _) = P128Pow5T5.constants();
+            poseidon::permute::<_, P128Pow5T5, WIDTH, RATE>(
+                &mut expected_final_state,
+                &mds,
+                &round_constants,
+            );
+
+            layouter.assign_region(
+                || "constrain final state",
+                |mut region| {
+                    for i in 0..WIDTH {
+                        let var = region.assign_advice(
+                            || format!("load final_state_{}", i),
+                            config.state[i],
+                            0,
+                            || Ok(expected_final_state[i]),
+                        )?;
+                        region.constrain_equal(final_state[i].var, var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn poseidon_permute() {
+        let k = 7;
+        let circuit = PermuteCircuit {};
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+}
@@ -0,0 +1,210 @@
+use crate::halo2::{
+    plonk::{Error},
+    circuit::{Layouter}
+};
+use pasta_curves::pallas;
+mod chip;
+
+use crate::gadget::select::{SelectInstruction};
+pub use chip::{QuaternaryMerkleConfig, QuaternaryMerkleChip};
+use crate::utils::{UtilitiesInstructions};
+
+pub trait QuaternaryMerkleInstructions: UtilitiesInstructions<pallas::Base> {
+    fn hash_layer(
+        &self,
+        layouter: impl Layouter<pallas::Base>,
+        children: [Self::Var; 4],
+        level: usize
+    ) -> Result<Self::Var, Error>;
+}
+
+/// Inclusion proof in a 4-ary tree, see [`crate::merkle::quaternary::QuaternaryTree`].
+#[derive(Clone, Debug)]
+pub struct QuaternaryInclusionProof<
+    const DEPTH: usize,
+>
+{
+    pub merkle_chip: QuaternaryMerkleChip,
+    pub siblings: [[Option<pallas::Base>; 3]; DEPTH],
+    pub leaf_pos: [Option<u8>; DEPTH],
+}
+
+impl
+<
+    const DEPTH: usize,
+>  QuaternaryInclusionProof<DEPTH>
+{
+    pub fn calculate_root(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        leaf: <QuaternaryMerkleChip as UtilitiesInstructions<pallas::Base>>::Var,
+    ) -> Result<<QuaternaryMerkleChip as UtilitiesInstructions<pallas::Base>>::Var, Error> {
+
+        let mut node = leaf;
+        let chip = &self.merkle_chip;
+
+        for (level, (siblings, pos)) in self.siblings.iter().zip(self.leaf_pos.iter()).enumerate() {
+            let children = chip.select(
+                layouter.namespace(|| format!("select children on level {}", level)),
+                node,
+                *siblings,
+                *pos
+            )?;
+
+            node = chip.hash_layer(
+                layouter.namespace(|| format!("hash level {}", level)),
+                children,
+                level
+            )?;
+        }
+
+        Ok(node)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::halo2::{
+        dev::MockProver,
+        pasta::Fp,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+    use pasta_curves::pallas;
+
+    use crate::utils::{UtilitiesInstructions, NumericCell};
+    use super::{QuaternaryMerkleChip, QuaternaryMerkleConfig, QuaternaryInclusionProof};
+    use crate::poseidon::P128Pow5T5;
+    use crate::gadget::poseidon::Pow5Chip;
+    use crate::merkle::quaternary::QuaternaryTree;
+
+    const DEPTH: usize = 4;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        advice: [Column<Advice>; 6],
+        instance: Column<Instance>,
+        merkle_config: QuaternaryMerkleConfig
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        leaf: Option<Fp>,
+        siblings: [[Option<Fp>; 3]; DEPTH],
+        leaf_pos: [Option<u8>; DEPTH]
+    }
+
+    impl UtilitiesInstructions<pallas::Base> for Circuit {
+        type Var = NumericCell<pallas::Base>;
+    }
+
+    impl plonk::Circuit<pallas::Base> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column()
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            for advice in advice.iter() {
+                meta.enable_equality((*advice).into());
+            }
+
+            let rc_a = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+            let rc_b = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+
+            meta.enable_constant(rc_b[0]);
+
+            let poseidon_config = Pow5Chip::configure(meta, P128Pow5T5, advice[0..5].try_into().unwrap(), advice[5], rc_a, rc_b);
+            let merkle_config = QuaternaryMerkleChip::configure(meta, advice[0..5].try_into().unwrap(), poseidon_config);
+
+            Config {
+                advice,
+                instance,
+                merkle_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<pallas::Base>,
+        ) -> Result<(), Error> {
+            let leaf = self.load_private(
+                layouter.namespace(|| "witness leaf"),
+                config.advice[0],
+                self.leaf,
+            )?;
+
+            let inclusion_proof = QuaternaryInclusionProof {
+                merkle_chip: QuaternaryMerkleChip::construct(config.merkle_config.clone()),
+                siblings: self.siblings,
+                leaf_pos: self.leaf_pos,
+            };
+
+            let root = inclusion_proof.calculate_root(layouter.namespace(|| "merkle root"), leaf)?;
+            self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, 0)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn quaternary_inclusion_proof_test() {
+        let k = 10;
+
+        let mut tree = QuaternaryTree::new(Fp::zero(), DEPTH);
+        for leaf in 1..=23u64 {
+            tree.insert(Fp::from(leaf));
+        }
+
+        // covers every position on the first two levels
+        for leaf in [1u64, 6, 11, 16, 23] {
+            let leaf = Fp::from(leaf);
+            let (siblings, pos) = tree.witness(leaf).unwrap();
+
+            let siblings: Vec<[Option<Fp>; 3]> = siblings.iter().map(|siblings| siblings.map(Some)).collect();
+            let pos: Vec<Option<u8>> = pos.iter().map(|pos| Some(*pos)).collect();
+
+            let circuit = Circuit {
+                leaf: Some(leaf),
+                siblings: siblings.try_into().expect("siblings with incorrect length"),
+                leaf_pos: pos.try_into().expect("pos with incorrect length")
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![vec![tree.root()]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+
+            let prover = MockProver::run(k, &circuit, vec![vec![tree.root() + Fp::one()]]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+}
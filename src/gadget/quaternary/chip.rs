@@ -0,0 +1,116 @@
+use crate::halo2::{
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error},
+};
+use pasta_curves::pallas;
+
+use crate::utils::{NumericCell, UtilitiesInstructions, Numeric, CellValue, Var};
+use crate::gadget::select::{SelectConfig, SelectChip, SelectInstruction};
+use crate::gadget::poseidon::{Pow5Config, Pow5Chip, Hash};
+use crate::poseidon::{P128Pow5T5, ConstantLength};
+
+use super::QuaternaryMerkleInstructions;
+
+#[derive(Clone, Debug)]
+pub struct QuaternaryMerkleConfig {
+    select_config: SelectConfig,
+    poseidon_config: Pow5Config<pallas::Base, 5, 4>,
+    advice: [Column<Advice>; 5]
+}
+
+#[derive(Clone, Debug)]
+pub struct QuaternaryMerkleChip {
+    config: QuaternaryMerkleConfig,
+}
+
+impl Chip<pallas::Base> for QuaternaryMerkleChip {
+    type Config = QuaternaryMerkleConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl UtilitiesInstructions<pallas::Base> for QuaternaryMerkleChip {
+    type Var = NumericCell<pallas::Base>;
+}
+
+impl QuaternaryMerkleChip {
+    pub fn configure(
+        meta: &mut ConstraintSystem<pallas::Base>,
+        advice: [Column<Advice>; 5],
+        poseidon_config: Pow5Config<pallas::Base, 5, 4>
+    ) -> QuaternaryMerkleConfig {
+        let select_config = SelectChip::configure(meta, advice);
+
+        QuaternaryMerkleConfig {
+            select_config,
+            poseidon_config,
+            advice
+        }
+    }
+
+    pub fn construct(config: QuaternaryMerkleConfig) -> Self {
+        QuaternaryMerkleChip {
+            config
+        }
+    }
+}
+
+impl SelectInstruction<pallas::Base> for QuaternaryMerkleChip {
+    fn select(
+        &self,
+        layouter: impl Layouter<pallas::Base>,
+        node: Self::Var,
+        siblings: [Option<pallas::Base>; 3],
+        pos: Option<u8>,
+    ) -> Result<[Self::Var; 4], Error> {
+        let config = self.config().select_config.clone();
+        let chip = SelectChip::<pallas::Base>::construct(config);
+        chip.select(layouter, node, siblings, pos)
+    }
+}
+
+impl QuaternaryMerkleInstructions for QuaternaryMerkleChip {
+    fn hash_layer(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        children: [Self::Var; 4],
+        level: usize,
+    ) -> Result<Self::Var, Error> {
+        let config = self.config.clone();
+        let chip = Pow5Chip::construct(config.poseidon_config.clone());
+
+        let hasher: Hash<
+            pallas::Base,
+            Pow5Chip<pallas::Base, 5, 4>,
+            P128Pow5T5,
+            ConstantLength<4>,
+            5_usize,
+            4_usize
+        > = Hash::init(chip, layouter.namespace(|| format!("init hasher {}", level)), ConstantLength::<4>)?;
+        let word = hasher.hash(layouter.namespace(|| format!("hash level {}", level)), children)?;
+        let digest: CellValue<pallas::Base> = word.inner().into();
+
+        // move the digest into a cell the select chip can copy from
+        layouter.assign_region(
+            || "witness parent",
+            |mut region| {
+                let cell = region.assign_advice(
+                    || "parent",
+                    config.advice[0],
+                    0,
+                    || digest.value().ok_or(Error::Synthesis),
+                )?;
+                region.constrain_equal(digest.cell(), cell.cell())?;
+
+                Ok(NumericCell::new(cell))
+            }
+        )
+    }
+}
@@ -0,0 +1,378 @@
+/*
+This chip constrains values to a fixed number of bits and compares them.
+
+Values are decomposed with a running sum starting from the most significant bit:
+acc_0 = bit_0, acc_i = 2 * acc_{i-1} + bit_i, and the last accumulator is the value itself.
+*/
+use crate::halo2::{
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector, Expression},
+    arithmetic::FieldExt,
+    poly::Rotation
+};
+use std::marker::PhantomData;
+use crate::utils::{NumericCell, UtilitiesInstructions, Numeric};
+
+pub trait RangeCheckInstructions<F: FieldExt>: UtilitiesInstructions<F> {
+    /// Constrains `value` to fit in `num_bits` bits and returns its bits, least significant first.
+    fn decompose(
+        &self,
+        layouter: impl Layouter<F>,
+        value: Self::Var,
+        num_bits: usize,
+    ) -> Result<Vec<Self::Var>, Error>;
+
+    /// Constrains `a < b`, where `a` and `b - a - 1` must both fit in `num_bits` bits.
+    fn less_than(
+        &self,
+        layouter: impl Layouter<F>,
+        a: Self::Var,
+        b: Self::Var,
+        num_bits: usize,
+    ) -> Result<(), Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeCheckConfig {
+    pub q_first: Selector,
+    pub q_step: Selector,
+    pub q_lt: Selector,
+    pub bit: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub diff: Column<Advice>
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeCheckChip<F> {
+    config: RangeCheckConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for RangeCheckChip<F> {
+    type Config = RangeCheckConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> UtilitiesInstructions<F> for RangeCheckChip<F> {
+    type Var = NumericCell<F>;
+}
+
+impl<F: FieldExt> RangeCheckChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advices: [Column<Advice>; 3],
+    ) -> RangeCheckConfig {
+        // values are copied into these columns
+        for advice in advices.iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let config = RangeCheckConfig {
+            q_first: meta.selector(),
+            q_step: meta.selector(),
+            q_lt: meta.selector(),
+            bit: advices[0],
+            acc: advices[1],
+            a: advices[0],
+            b: advices[1],
+            diff: advices[2],
+        };
+
+        meta.create_gate("range check first bit", |meta| {
+            let q_first = meta.query_selector(config.q_first);
+
+            let bit = meta.query_advice(config.bit, Rotation::cur());
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+
+            let one = Expression::Constant(F::one());
+
+            let check_acc = acc - bit.clone();
+            let check_bool = bit.clone() * (one - bit);
+
+            [check_acc, check_bool]
+                .into_iter()
+                .map(move |poly| q_first.clone() * poly)
+        });
+
+        meta.create_gate("range check step", |meta| {
+            let q_step = meta.query_selector(config.q_step);
+
+            let bit = meta.query_advice(config.bit, Rotation::cur());
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+            let acc_prev = meta.query_advice(config.acc, Rotation::prev());
+
+            let one = Expression::Constant(F::one());
+            let two = Expression::Constant(F::from(2));
+
+            let check_acc = acc - acc_prev * two - bit.clone();
+            let check_bool = bit.clone() * (one - bit);
+
+            [check_acc, check_bool]
+                .into_iter()
+                .map(move |poly| q_step.clone() * poly)
+        });
+
+        meta.create_gate("less than", |meta| {
+            let q_lt = meta.query_selector(config.q_lt);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let diff = meta.query_advice(config.diff, Rotation::cur());
+
+            let one = Expression::Constant(F::one());
+
+            // diff = b - a - 1
+            [b - a - one - diff]
+                .into_iter()
+                .map(move |poly| q_lt.clone() * poly)
+        });
+
+        config
+    }
+
+    pub fn construct(config: RangeCheckConfig) -> Self {
+        RangeCheckChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+}
+
+/// Returns bit `i` of the canonical little-endian encoding of `value`.
+fn bit_at<F: FieldExt>(value: F, i: usize) -> bool {
+    let repr = value.to_repr();
+    (repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1
+}
+
+impl<F: FieldExt> RangeCheckInstructions<F> for RangeCheckChip<F> {
+    fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Self::Var,
+        num_bits: usize,
+    ) -> Result<Vec<Self::Var>, Error> {
+        assert!(num_bits > 0 && num_bits < F::NUM_BITS as usize);
+        let config = self.config();
+
+        layouter.assign_region(
+            || "decompose",
+            |mut region| {
+                let mut bits = Vec::with_capacity(num_bits);
+                let mut acc_value = value.value().map(|_| F::zero());
+
+                for row_offset in 0..num_bits {
+                    let bit_index = num_bits - 1 - row_offset;
+
+                    if row_offset == 0 {
+                        config.q_first.enable(&mut region, row_offset)?;
+                    } else {
+                        config.q_step.enable(&mut region, row_offset)?;
+                    }
+
+                    let bit_value = value.value().map(|value| bit_at(value, bit_index));
+                    let bit = {
+                        let cell = region.assign_advice(
+                            || format!("witness bit {}", bit_index),
+                            config.bit,
+                            row_offset,
+                            || bit_value.map(|bit| F::from(bit as u64)).ok_or(Error::Synthesis),
+                        )?;
+                        NumericCell::new(cell)
+                    };
+                    bits.push(bit);
+
+                    acc_value = acc_value
+                        .zip(bit_value)
+                        .map(|(acc, bit)| acc * F::from(2) + F::from(bit as u64));
+
+                    // the last accumulator must be the decomposed value itself
+                    if row_offset == num_bits - 1 {
+                        value.copy(|| "copy value", &mut region, config.acc, row_offset)?;
+                    } else {
+                        region.assign_advice(
+                            || format!("witness acc {}", row_offset),
+                            config.acc,
+                            row_offset,
+                            || acc_value.ok_or(Error::Synthesis),
+                        )?;
+                    }
+                }
+
+                bits.reverse();
+                Ok(bits)
+            }
+        )
+    }
+
+    fn less_than(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Self::Var,
+        b: Self::Var,
+        num_bits: usize,
+    ) -> Result<(), Error> {
+        let config = self.config();
+
+        let diff = layouter.assign_region(
+            || "less than",
+            |mut region| {
+                let row_offset = 0;
+                config.q_lt.enable(&mut region, row_offset)?;
+
+                let a = a.copy(|| "copy a", &mut region, config.a, row_offset)?;
+                let b = b.copy(|| "copy b", &mut region, config.b, row_offset)?;
+
+                let diff = a
+                    .value()
+                    .zip(b.value())
+                    .map(|(a, b)| b - a - F::one());
+
+                let cell = region.assign_advice(
+                    || "witness diff",
+                    config.diff,
+                    row_offset,
+                    || diff.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(NumericCell::new(cell))
+            }
+        )?;
+
+        // with a and b - a - 1 both below 2^num_bits, b = a + 1 + diff cannot wrap
+        // around the modulus, so a < b holds over the integers
+        self.decompose(layouter.namespace(|| "range check a"), a, num_bits)?;
+        self.decompose(layouter.namespace(|| "range check diff"), diff, num_bits)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::halo2::{
+        dev::MockProver,
+        pasta::Fp,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Column, ConstraintSystem, Error},
+        plonk,
+    };
+
+    use pasta_curves::pallas;
+
+    use super::{RangeCheckChip, RangeCheckConfig, RangeCheckInstructions};
+
+    use crate::utils::{UtilitiesInstructions, NumericCell};
+
+    const NUM_BITS: usize = 8;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        advice: [Column<Advice>; 3],
+        range_config: RangeCheckConfig
+    }
+
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        a: Option<Fp>,
+        b: Option<Fp>,
+    }
+
+    impl UtilitiesInstructions<pallas::Base> for Circuit {
+        type Var = NumericCell<pallas::Base>;
+    }
+
+    impl plonk::Circuit<pallas::Base> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+
+            let range_config = RangeCheckChip::configure(meta, advice);
+
+            Config {
+                advice,
+                range_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<pallas::Base>,
+        ) -> Result<(), Error> {
+            let config = config.clone();
+
+            let a = self.load_private(
+                layouter.namespace(|| "witness a"),
+                config.advice[0],
+                self.a,
+            )?;
+
+            let b = self.load_private(
+                layouter.namespace(|| "witness b"),
+                config.advice[0],
+                self.b,
+            )?;
+
+            let chip = RangeCheckChip::<pallas::Base>::construct(config.range_config.clone());
+
+            chip.less_than(layouter.namespace(|| "a < b"), a, b, NUM_BITS)?;
+
+            Ok(())
+        }
+    }
+
+    fn run(a: Fp, b: Fp) -> bool {
+        let k = 6;
+
+        let circuit = Circuit {
+            a: Some(a),
+            b: Some(b),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.verify().is_ok()
+    }
+
+    #[test]
+    fn less_than_test() {
+        assert!(run(Fp::from(0), Fp::from(1)));
+        assert!(run(Fp::from(3), Fp::from(10)));
+        assert!(run(Fp::from(254), Fp::from(255)));
+    }
+
+    #[test]
+    fn not_less_than_test() {
+        assert!(!run(Fp::from(1), Fp::from(1)));
+        assert!(!run(Fp::from(10), Fp::from(3)));
+        // a wraps around the modulus
+        assert!(!run(-Fp::one(), Fp::from(3)));
+    }
+
+    #[test]
+    fn out_of_range_test() {
+        assert!(!run(Fp::from(300), Fp::from(400)));
+        assert!(!run(Fp::from(0), Fp::from(300)));
+    }
+}
@@ -10,11 +10,19 @@ use crate::utils::{UtilitiesInstructions};
 pub use chip::{RlnConfig, RlnChip};
 
 pub(crate) trait RlnInstructions<F: FieldExt>: UtilitiesInstructions<F> {
+    fn calculate_external_nullifier(
+        &self,
+        layouter: impl Layouter<F>,
+        epoch: Self::Var,
+        rln_identifier: Self::Var,
+    ) -> Result<Self::Var, Error>;
+
     fn calculate_output(
         &self,
         layouter: impl Layouter<F>,
         private_key: Self::Var, 
-        epoch: Self::Var,
+        external_nullifier: Self::Var,
+        message_id: Self::Var,
         signal: Self::Var,
     ) -> Result<(Self::Var, Self::Var), Error>;
 
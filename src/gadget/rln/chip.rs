@@ -13,7 +13,7 @@ use std::array;
 use super::RlnInstructions;
 
 use crate::{
-    utils::{NumericCell, Numeric, UtilitiesInstructions, from_cell_vale_to_numeric, CellValue, Var},
+    utils::{NumericCell, Numeric, UtilitiesInstructions, from_cell_vale_to_numeric, CellValue},
     gadget::poseidon::{HashInstruction, Pow5T3Config as PoseidonConfig, Pow5T3Chip as PoseidonChip, Hash},
     poseidon::{ConstantLength, P128Pow5T3}
 };
@@ -118,22 +118,55 @@ impl<const LEN: usize> HashInstruction<pallas::Base, LEN> for RlnChip<pallas::Ba
         >  = Hash::init(chip, layouter.namespace(|| "init hasher"), ConstantLength::<LEN>)?;
         let word = poseidon_hasher.hash(layouter.namespace(|| "digest message"), message)?;
         let digest: CellValue<pallas::Base> = word.inner().into();
-        let assigned = from_cell_vale_to_numeric(layouter.namespace(|| "dummy"), config.n, digest.value())?;
+        let assigned = from_cell_vale_to_numeric(layouter.namespace(|| "dummy"), config.n, digest)?;
         Ok(assigned)
     }
 }
 
 impl RlnInstructions<pallas::Base> for RlnChip<pallas::Base> {
+    fn calculate_external_nullifier(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        epoch: Self::Var,
+        rln_identifier: Self::Var,
+    ) -> Result<Self::Var, Error> {
+        self.hash(layouter.namespace(|| "hash to external nullifier"), [epoch, rln_identifier])
+    }
+
     fn calculate_output(
         &self,
         mut layouter: impl Layouter<pallas::Base>,
         private_key: Self::Var, 
-        epoch: Self::Var,
+        external_nullifier: Self::Var,
+        message_id: Self::Var,
         signal: Self::Var,
     ) -> Result<(Self::Var, Self::Var), Error> {
-        let config = self.config();
+        // k = Poseidon(Poseidon(private_key, external_nullifier), message_id)
+        let epoch_key = self.hash(layouter.namespace(|| "hash to epoch key"), [private_key.clone(), external_nullifier])?;
+        let hashed = self.hash(layouter.namespace(|| "hash to k"), [epoch_key, message_id])?;
 
-        let hashed = self.hash(layouter.namespace(|| "hash to k"), [private_key.clone(), epoch])?;
+        self.calculate_share(layouter.namespace(|| "calculate share"), private_key, hashed, signal)
+    }
+
+    fn calculate_nullifier(
+        &self, 
+        mut layouter: impl Layouter<pallas::Base>,
+        y: Self::Var
+    ) -> Result<Self::Var, Error> {
+        self.hash(layouter.namespace(|| "calculate nullifier"), [y])
+    }
+}
+
+impl RlnChip<pallas::Base> {
+    /// Computes the share `y = k * signal + private_key`, returning it with the copy of `k` it uses.
+    fn calculate_share(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        private_key: NumericCell<pallas::Base>,
+        k: NumericCell<pallas::Base>,
+        signal: NumericCell<pallas::Base>,
+    ) -> Result<(NumericCell<pallas::Base>, NumericCell<pallas::Base>), Error> {
+        let config = self.config();
 
         layouter.assign_region(
             || "rln", 
@@ -142,19 +175,10 @@ impl RlnInstructions<pallas::Base> for RlnChip<pallas::Base> {
 
                 let n = private_key.copy(|| "copy pk", &mut region, config.n, row_offset)?;
                 let x = signal.copy(|| "copy x", &mut region, config.x, row_offset)?;
+                let k = k.copy(|| "copy k", &mut region, config.k, row_offset)?;
 
                 config.q_rln.enable(&mut region, row_offset)?;
 
-                let k = {
-                    let cell = region.assign_advice(
-                        || "witness k",
-                        config.k,
-                        row_offset,
-                        || hashed.value().ok_or(Error::Synthesis),
-                    )?;
-                    NumericCell::new(cell)
-                };
-
                 row_offset += 1;
                 let y = {
                     let y = k
@@ -162,9 +186,8 @@ impl RlnInstructions<pallas::Base> for RlnChip<pallas::Base> {
                         .zip(x.value())
                         .zip(n.value())
                         .map(|((k, x), n)| k * x + n);
-                    // let y = k.value();
                     let cell = region.assign_advice(
-                        || "witness k",
+                        || "witness y",
                         config.n,
                         row_offset,
                         || y.ok_or(Error::Synthesis),
@@ -176,31 +199,6 @@ impl RlnInstructions<pallas::Base> for RlnChip<pallas::Base> {
             }
         )
     }
-
-    fn calculate_nullifier(
-        &self, 
-        mut layouter: impl Layouter<pallas::Base>,
-        y: Self::Var
-    ) -> Result<Self::Var, Error> {
-        let config = self.config().clone();
-        let nullifier = self.hash(layouter.namespace(|| "calculate nullifier"), [y])?;
-
-        layouter.assign_region(
-            || "witness nullifier",
-            |mut region| {
-                let row_offset = 0;
-                let cell = region.assign_advice(
-                    || "nullifier",
-                    config.n,
-                    row_offset,
-                    || nullifier.value().ok_or(Error::Synthesis),
-                )?;
-
-                Ok(NumericCell::new(cell))
-            }
-        )
-
-    }
 }
 
 #[cfg(test)]
@@ -217,16 +215,32 @@ mod test {
 
     use super::{RlnChip, RlnConfig, RlnInstructions};
 
-    use crate::utils::{UtilitiesInstructions, NumericCell};
+    use crate::utils::{UtilitiesInstructions, NumericCell, Numeric, CellValue, Var, from_cell_vale_to_numeric};
     use crate::poseidon::{ConstantLength, P128Pow5T3, Hash};
-    use crate::gadget::poseidon::{Pow5T3Chip as PoseidonChip};
-    use crate::client::calculate_output;
+    use crate::gadget::poseidon::{Pow5T3Chip as PoseidonChip, HashInstruction};
+    use crate::gadget::range::{RangeCheckChip, RangeCheckConfig, RangeCheckInstructions};
+    use crate::client::{calculate_output, external_nullifier};
+
+    /// Value a dishonest prover assigns to an intermediate cell instead of the computed one.
+    #[derive(Clone, Copy, Debug)]
+    enum Forge {
+        Nothing,
+        ExternalNullifier(Fp),
+        K(Fp),
+    }
+
+    impl Default for Forge {
+        fn default() -> Self {
+            Forge::Nothing
+        }
+    }
 
     #[derive(Clone, Debug)]
     pub struct Config {
         advice: [Column<Advice>; 4],
         instance: Column<Instance>,
-        rln_config: RlnConfig<pallas::Base>
+        rln_config: RlnConfig<pallas::Base>,
+        range_config: RangeCheckConfig
     }
 
 
@@ -234,13 +248,31 @@ mod test {
     pub struct Circuit {
         private_key: Option<Fp>,
         epoch: Option<Fp>,
+        rln_identifier: Option<Fp>,
+        message_id: Option<Fp>,
+        user_message_limit: Option<Fp>,
         signal: Option<Fp>,
+        forge: Forge,
     }
 
     impl UtilitiesInstructions<pallas::Base> for Circuit {
         type Var = NumericCell<pallas::Base>;
     }
 
+    impl Circuit {
+        /// Puts `value` in a cell wired to `cell` the way hash outputs are, as a prover
+        /// filling the circuit with other values than the computed ones would.
+        fn forge(
+            &self,
+            layouter: impl Layouter<pallas::Base>,
+            column: Column<Advice>,
+            cell: NumericCell<pallas::Base>,
+            value: Fp,
+        ) -> Result<NumericCell<pallas::Base>, Error> {
+            from_cell_vale_to_numeric(layouter, column, CellValue::new(cell.cell(), Some(value)))
+        }
+    }
+
     impl plonk::Circuit<pallas::Base> for Circuit {
         type Config = Config;
         type FloorPlanner = SimpleFloorPlanner;
@@ -280,11 +312,13 @@ mod test {
 
         let poseidon_config = PoseidonChip::configure(meta, P128Pow5T3, advice[0..3].try_into().unwrap(), advice[3], rc_a, rc_b);
         let rln_config = RlnChip::<pallas::Base>::configure(meta, advice[..3].try_into().unwrap(), poseidon_config);
+        let range_config = RangeCheckChip::<pallas::Base>::configure(meta, advice[..3].try_into().unwrap());
 
             Config {
                 advice,
                 instance,
-                rln_config
+                rln_config,
+                range_config
             }
         }
 
@@ -307,43 +341,131 @@ mod test {
                 self.epoch,
             )?;
 
+            let rln_identifier = self.load_private(
+                layouter.namespace(|| "witness rln_identifier"),
+                config.advice[0],
+                self.rln_identifier,
+            )?;
+
+            let message_id = self.load_private(
+                layouter.namespace(|| "witness message_id"),
+                config.advice[0],
+                self.message_id,
+            )?;
+
+            let user_message_limit = self.load_private(
+                layouter.namespace(|| "witness user_message_limit"),
+                config.advice[0],
+                self.user_message_limit,
+            )?;
+
             let signal = self.load_private(
                 layouter.namespace(|| "witness identity_trapdoor"),
                 config.advice[0],
                 self.signal,
             )?;
 
+            let range_chip = RangeCheckChip::construct(config.range_config);
+            range_chip.less_than(layouter.namespace(|| "message_id < user_message_limit"), message_id.clone(), user_message_limit, 32)?;
+
             let rln_chip = RlnChip::construct(config.rln_config);
-            let (y, k) = rln_chip.calculate_output(layouter.namespace(|| "calculate y"), private_key, epoch, signal.clone())?;
-            let nullifier = rln_chip.calculate_nullifier(layouter.namespace(|| "calculate nullifier"), k.clone())?;
+            let mut external_nullifier = rln_chip.calculate_external_nullifier(layouter.namespace(|| "calculate external nullifier"), epoch.clone(), rln_identifier.clone())?;
+            if let Forge::ExternalNullifier(value) = self.forge {
+                external_nullifier = self.forge(layouter.namespace(|| "forge external nullifier"), config.advice[0], external_nullifier, value)?;
+            }
+
+            // calculate_output with the derived k open to forgery
+            let epoch_key = rln_chip.hash(layouter.namespace(|| "hash to epoch key"), [private_key.clone(), external_nullifier])?;
+            let mut k = rln_chip.hash(layouter.namespace(|| "hash to k"), [epoch_key, message_id])?;
+            if let Forge::K(value) = self.forge {
+                k = self.forge(layouter.namespace(|| "forge k"), config.advice[0], k, value)?;
+            }
+            let (y, k) = rln_chip.calculate_share(layouter.namespace(|| "calculate y"), private_key, k, signal.clone())?;
+            let nullifier = rln_chip.calculate_nullifier(layouter.namespace(|| "calculate nullifier"), k)?;
 
             self.expose_public(layouter.namespace(|| "expose y"), config.instance, y, 0)?;
             self.expose_public(layouter.namespace(|| "expose nullifier"), config.instance, nullifier, 1)?;
             self.expose_public(layouter.namespace(|| "expose signal"), config.instance, signal, 2)?;
+            self.expose_public(layouter.namespace(|| "expose rln_identifier"), config.instance, rln_identifier, 3)?;
+            self.expose_public(layouter.namespace(|| "expose epoch"), config.instance, epoch, 4)?;
 
             Ok(())
         }
     }
 
+    const K: u32 = 10;
+    const MSG: &str = "hello rln";
+
+    fn circuit(message_id: u64, forge: Forge) -> Circuit {
+        Circuit {
+            private_key: Some(Fp::from(5)),
+            epoch: Some(Fp::from(2)),
+            rln_identifier: Some(Fp::from(3)),
+            message_id: Some(Fp::from(message_id)),
+            user_message_limit: Some(Fp::from(2)),
+            signal: Some(crate::hash_to_field::hash_to_field(MSG.as_bytes())),
+            forge,
+        }
+    }
+
+    /// Public inputs of a signal of the member of `circuit` for the given epoch and message.
+    fn public_inputs(circuit: &Circuit, epoch: Fp, message_id: u64) -> Vec<Fp> {
+        let private_key = circuit.private_key.unwrap();
+        let rln_identifier = circuit.rln_identifier.unwrap();
+        let (y, nullifier, signal) = calculate_output(private_key, epoch, rln_identifier, Fp::from(message_id), MSG);
+        vec![y, nullifier, signal, rln_identifier, circuit.epoch.unwrap()]
+    }
+
+    fn k(private_key: Fp, external_nullifier: Fp, message_id: u64) -> Fp {
+        let epoch_key = Hash::init(P128Pow5T3, ConstantLength::<2>).hash([private_key, external_nullifier]);
+        Hash::init(P128Pow5T3, ConstantLength::<2>).hash([epoch_key, Fp::from(message_id)])
+    }
+
+    fn verifies(circuit: &Circuit, public_inputs: Vec<Fp>) -> bool {
+        MockProver::run(K, circuit, vec![public_inputs]).unwrap().verify().is_ok()
+    }
+
     #[test]
     fn rln_test() {
-        let k = 9;
+        let circuit = circuit(1, Forge::Nothing);
+        let public_inputs = public_inputs(&circuit, Fp::from(2), 1);
+        assert!(verifies(&circuit, public_inputs));
+    }
 
-        let private_key = Some(Fp::from(5));
-        let epoch = Some(Fp::from(2));
+    #[test]
+    fn forged_k() {
+        let private_key = Fp::from(5);
+        let external = external_nullifier(Fp::from(2), Fp::from(3));
 
-        let msg = "hello rln";
+        // forging k with its own value changes nothing
+        let honest = circuit(1, Forge::K(k(private_key, external, 1)));
+        assert!(verifies(&honest, public_inputs(&honest, Fp::from(2), 1)));
 
-        let (y, nullifier, signal) = calculate_output(private_key.unwrap(), epoch.unwrap(), msg);
-    
-        let circuit = Circuit {
-            private_key,
-            epoch,
-            signal: Some(signal)
-        };
+        // a k for message_id = user_message_limit gives a fresh nullifier, the range check passes on 1
+        let forged = circuit(1, Forge::K(k(private_key, external, 2)));
+        assert!(!verifies(&forged, public_inputs(&forged, Fp::from(2), 2)));
+    }
+
+    #[test]
+    fn forged_external_nullifier() {
+        let private_key = Fp::from(5);
 
-        let public_inputs = vec![y, nullifier, signal];
-        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
-        assert_eq!(prover.verify(), Ok(()));
+        // forging the external nullifier with its own value changes nothing
+        let honest = circuit(1, Forge::ExternalNullifier(external_nullifier(Fp::from(2), Fp::from(3))));
+        assert!(verifies(&honest, public_inputs(&honest, Fp::from(2), 1)));
+
+        // shares and nullifier of another app presented under rln_identifier 3
+        let forged = circuit(1, Forge::ExternalNullifier(external_nullifier(Fp::from(2), Fp::from(4))));
+        let (y, nullifier, signal) = calculate_output(private_key, Fp::from(2), Fp::from(4), Fp::from(1), MSG);
+        assert!(!verifies(&forged, vec![y, nullifier, signal, Fp::from(3), Fp::from(2)]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn forged_epoch() {
+        // the external nullifier of epoch 7 with epoch 2 exposed, so the member gets a
+        // fresh nullifier in the public epoch
+        let forged = circuit(1, Forge::ExternalNullifier(external_nullifier(Fp::from(7), Fp::from(3))));
+        assert!(!verifies(&forged, public_inputs(&forged, Fp::from(7), 1)));
+        assert!(!verifies(&forged, public_inputs(&forged, Fp::from(2), 1)));
+    }
+}
@@ -0,0 +1,310 @@
+/*
+This chip orders a node and its three siblings into the four inputs of the hasher,
+based on the position of the node at the current level of a 4-ary tree.
+
+The position is witnessed as two bits b0 + 2 * b1 and the node goes to that slot,
+the siblings fill the other slots from left to right. With the indicators
+l_i = [position == i], each child is:
+
+c0 = l0 * node + (1 - l0) * s0
+c1 = l1 * node + l0 * s0 + (l2 + l3) * s1
+c2 = l2 * node + (l0 + l1) * s1 + l3 * s2
+c3 = l3 * node + (1 - l3) * s2
+
+Like the swap chip, the node is expected to be already witnessed.
+*/
+use crate::halo2::{
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector, Expression},
+    arithmetic::FieldExt,
+    poly::Rotation
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+use crate::utils::{NumericCell, UtilitiesInstructions, Numeric};
+
+pub trait SelectInstruction<F: FieldExt>: UtilitiesInstructions<F> {
+    /// Returns the four children with `node` at `pos`, from 0 to 3.
+    fn select(
+        &self,
+        layouter: impl Layouter<F>,
+        node: Self::Var,
+        siblings: [Option<F>; 3],
+        pos: Option<u8>,
+    ) -> Result<[Self::Var; 4], Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SelectConfig {
+    pub q_select: Selector,
+    // node and siblings on the first row, children on the second
+    pub values: [Column<Advice>; 4],
+    // low bit of the position on the first row, high bit on the second
+    pub bits: Column<Advice>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SelectChip<F> {
+    config: SelectConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for SelectChip<F> {
+    type Config = SelectConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> UtilitiesInstructions<F> for SelectChip<F> {
+    type Var = NumericCell<F>;
+}
+
+impl<F: FieldExt> SelectChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advices: [Column<Advice>; 5],
+    ) -> SelectConfig {
+        // the node is copied in and the children are copied out
+        for advice in advices[0..4].iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let config = SelectConfig {
+            q_select: meta.selector(),
+            values: advices[0..4].try_into().unwrap(),
+            bits: advices[4],
+        };
+
+        meta.create_gate("constraint select", |meta| {
+            let q_select = meta.query_selector(config.q_select);
+
+            let node = meta.query_advice(config.values[0], Rotation::cur());
+            let s0 = meta.query_advice(config.values[1], Rotation::cur());
+            let s1 = meta.query_advice(config.values[2], Rotation::cur());
+            let s2 = meta.query_advice(config.values[3], Rotation::cur());
+
+            let c0 = meta.query_advice(config.values[0], Rotation::next());
+            let c1 = meta.query_advice(config.values[1], Rotation::next());
+            let c2 = meta.query_advice(config.values[2], Rotation::next());
+            let c3 = meta.query_advice(config.values[3], Rotation::next());
+
+            let b0 = meta.query_advice(config.bits, Rotation::cur());
+            let b1 = meta.query_advice(config.bits, Rotation::next());
+
+            let one = Expression::Constant(F::one());
+
+            let l0 = (one.clone() - b0.clone()) * (one.clone() - b1.clone());
+            let l1 = b0.clone() * (one.clone() - b1.clone());
+            let l2 = (one.clone() - b0.clone()) * b1.clone();
+            let l3 = b0.clone() * b1.clone();
+
+            let check_c0 = c0 - l0.clone() * node.clone() - (one.clone() - l0.clone()) * s0.clone();
+            let check_c1 = c1 - l1.clone() * node.clone() - l0.clone() * s0 - (l2.clone() + l3.clone()) * s1.clone();
+            let check_c2 = c2 - l2 * node.clone() - (l0 + l1) * s1 - l3.clone() * s2.clone();
+            let check_c3 = c3 - l3.clone() * node - (one.clone() - l3) * s2;
+
+            let check_b0 = b0.clone() * (one.clone() - b0);
+            let check_b1 = b1.clone() * (one - b1);
+
+            [check_c0, check_c1, check_c2, check_c3, check_b0, check_b1]
+                .into_iter()
+                .map(move |poly| q_select.clone() * poly)
+        });
+
+        config
+    }
+
+    pub fn construct(config: SelectConfig) -> Self {
+        SelectChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+}
+
+impl<F: FieldExt> SelectInstruction<F> for SelectChip<F> {
+    fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        node: Self::Var,
+        siblings: [Option<F>; 3],
+        pos: Option<u8>,
+    ) -> Result<[Self::Var; 4], Error> {
+        let config = self.config();
+
+        if let Some(pos) = pos {
+            assert!(pos < 4, "position must be below 4");
+        }
+
+        layouter.assign_region(
+            || "select",
+            |mut region| {
+                config.q_select.enable(&mut region, 0)?;
+
+                let node = node.copy(|| "copy node", &mut region, config.values[0], 0)?;
+
+                for (i, sibling) in siblings.iter().enumerate() {
+                    region.assign_advice(
+                        || format!("witness sibling {}", i),
+                        config.values[i + 1],
+                        0,
+                        || sibling.ok_or(Error::Synthesis),
+                    )?;
+                }
+
+                // bit i of the position goes on row i
+                for row_offset in 0..2 {
+                    let bit = pos.map(|pos| F::from(((pos >> row_offset) & 1) as u64));
+                    region.assign_advice(
+                        || format!("witness bit {}", row_offset),
+                        config.bits,
+                        row_offset,
+                        || bit.ok_or(Error::Synthesis),
+                    )?;
+                }
+
+                let mut children = Vec::with_capacity(4);
+                for i in 0..4 {
+                    let child = node.value().zip(pos).and_then(|(node, pos)| {
+                        let pos = pos as usize;
+                        match i {
+                            i if i == pos => Some(node),
+                            i if i < pos => siblings[i],
+                            i => siblings[i - 1],
+                        }
+                    });
+
+                    let cell = region.assign_advice(
+                        || format!("witness child {}", i),
+                        config.values[i],
+                        1,
+                        || child.ok_or(Error::Synthesis),
+                    )?;
+                    children.push(NumericCell::new(cell));
+                }
+
+                Ok(children.try_into().unwrap())
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::halo2::{
+        dev::MockProver,
+        pasta::Fp,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+
+    use pasta_curves::pallas;
+
+    use super::{SelectChip, SelectConfig, SelectInstruction};
+
+    use crate::utils::{UtilitiesInstructions, NumericCell};
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        advice: [Column<Advice>; 5],
+        instance: Column<Instance>,
+        select_config: SelectConfig
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        node: Option<Fp>,
+        siblings: [Option<Fp>; 3],
+        pos: Option<u8>
+    }
+
+    impl UtilitiesInstructions<pallas::Base> for Circuit {
+        type Var = NumericCell<pallas::Base>;
+    }
+
+    impl plonk::Circuit<pallas::Base> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let select_config = SelectChip::configure(meta, advice);
+
+            Config {
+                advice,
+                instance,
+                select_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<pallas::Base>,
+        ) -> Result<(), Error> {
+            let node = self.load_private(
+                layouter.namespace(|| "witness node"),
+                config.advice[0],
+                self.node,
+            )?;
+
+            let chip = SelectChip::<pallas::Base>::construct(config.select_config.clone());
+            let children = chip.select(layouter.namespace(|| "select"), node, self.siblings, self.pos)?;
+
+            for (row, child) in children.iter().enumerate() {
+                self.expose_public(layouter.namespace(|| format!("expose child {}", row)), config.instance, child.clone(), row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn select_test() {
+        let k = 4;
+        let node = Fp::from(10);
+        let siblings = [Fp::from(1), Fp::from(2), Fp::from(3)];
+
+        for pos in 0..4u8 {
+            let circuit = Circuit {
+                node: Some(node),
+                siblings: siblings.map(Some),
+                pos: Some(pos)
+            };
+
+            let mut children = siblings.to_vec();
+            children.insert(pos as usize, node);
+
+            let prover = MockProver::run(k, &circuit, vec![children.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+
+            // the node must be at `pos`
+            children.swap(pos as usize, (pos as usize + 1) % 4);
+            let prover = MockProver::run(k, &circuit, vec![children]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+}
@@ -0,0 +1,389 @@
+/*
+This gadget proves that a key is not in a sparse Merkle tree.
+
+The key is decomposed into `DEPTH` bits, which select its slot and constrain it below
+2^DEPTH, so that every key has a path of its own. The slot holds a leaf different from
+the key, which in a tree built by `SparseMerkleTree` is the empty leaf, and the leaf
+hashes up to the root along that path.
+*/
+use crate::halo2::{
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector, Expression},
+    poly::Rotation
+};
+use ff::Field;
+use pasta_curves::pallas;
+use std::marker::PhantomData;
+
+use crate::gadget::merkle::{MerkleChip, MerkleConfig, MerkleInstructions};
+use crate::gadget::range::{RangeCheckChip, RangeCheckConfig, RangeCheckInstructions};
+use crate::gadget::swap::SwapInstruction;
+use crate::merkle::sparse::KEY_BITS;
+use crate::utils::{NumericCell, UtilitiesInstructions, Numeric};
+
+#[derive(Clone, Debug)]
+pub struct SparseMerkleConfig {
+    pub q_neq: Selector,
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub inv: Column<Advice>,
+    pub merkle_config: MerkleConfig<pallas::Base>,
+    pub range_config: RangeCheckConfig,
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseMerkleChip {
+    config: SparseMerkleConfig,
+}
+
+impl Chip<pallas::Base> for SparseMerkleChip {
+    type Config = SparseMerkleConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl UtilitiesInstructions<pallas::Base> for SparseMerkleChip {
+    type Var = NumericCell<pallas::Base>;
+}
+
+impl SparseMerkleChip {
+    pub fn configure(
+        meta: &mut ConstraintSystem<pallas::Base>,
+        advices: [Column<Advice>; 3],
+        merkle_config: MerkleConfig<pallas::Base>,
+        range_config: RangeCheckConfig,
+    ) -> SparseMerkleConfig {
+        for advice in advices[0..2].iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let config = SparseMerkleConfig {
+            q_neq: meta.selector(),
+            a: advices[0],
+            b: advices[1],
+            inv: advices[2],
+            merkle_config,
+            range_config,
+        };
+
+        meta.create_gate("not equal", |meta| {
+            let q_neq = meta.query_selector(config.q_neq);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let inv = meta.query_advice(config.inv, Rotation::cur());
+
+            let one = Expression::Constant(pallas::Base::one());
+
+            // a - b has an inverse only when a != b
+            [(a - b) * inv - one]
+                .into_iter()
+                .map(move |poly| q_neq.clone() * poly)
+        });
+
+        config
+    }
+
+    pub fn construct(config: SparseMerkleConfig) -> Self {
+        SparseMerkleChip {
+            config
+        }
+    }
+
+    /// Constrains `a != b`.
+    pub fn not_equal(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        a: NumericCell<pallas::Base>,
+        b: NumericCell<pallas::Base>,
+    ) -> Result<(), Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "not equal",
+            |mut region| {
+                let row_offset = 0;
+                config.q_neq.enable(&mut region, row_offset)?;
+
+                let a = a.copy(|| "copy a", &mut region, config.a, row_offset)?;
+                let b = b.copy(|| "copy b", &mut region, config.b, row_offset)?;
+
+                // a zero difference has no inverse, witness zero and let the gate fail
+                let inv = a
+                    .value()
+                    .zip(b.value())
+                    .map(|(a, b)| (a - b).invert().unwrap_or(pallas::Base::zero()));
+
+                region.assign_advice(
+                    || "witness inverse",
+                    config.inv,
+                    row_offset,
+                    || inv.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(())
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NonMembershipProof<
+    const DEPTH: usize,
+>
+{
+    pub chip: SparseMerkleChip,
+    pub leaf: Option<pallas::Base>,
+    pub siblings: [Option<pallas::Base>; DEPTH],
+    pub _marker: PhantomData<pallas::Base>,
+}
+
+impl
+<
+    const DEPTH: usize,
+>  NonMembershipProof<DEPTH>
+{
+    /// Returns the root of a tree in which `key` is absent.
+    pub fn calculate_root(
+        &self,
+        mut layouter: impl Layouter<pallas::Base>,
+        key: NumericCell<pallas::Base>,
+    ) -> Result<NumericCell<pallas::Base>, Error> {
+        assert!(DEPTH <= KEY_BITS);
+        let config = self.chip.config().clone();
+
+        let range_chip = RangeCheckChip::<pallas::Base>::construct(config.range_config.clone());
+        let bits = range_chip.decompose(layouter.namespace(|| "key bits"), key.clone(), DEPTH)?;
+
+        let leaf = self.chip.load_private(
+            layouter.namespace(|| "witness leaf"),
+            config.a,
+            self.leaf,
+        )?;
+        self.chip.not_equal(layouter.namespace(|| "leaf is not key"), leaf.clone(), key)?;
+
+        let merkle_chip = MerkleChip::construct(config.merkle_config.clone());
+        let mut node = leaf;
+
+        for (level, (sibling, bit)) in self.siblings.iter().zip(bits).enumerate() {
+            let pair = merkle_chip.swap_with_bit(
+                layouter.namespace(|| format!("swap pair on level {}", level)),
+                (node, *sibling),
+                bit,
+            )?;
+
+            node = merkle_chip.hash_layer(
+                layouter.namespace(|| format!("hash level {}", level)),
+                pair.0,
+                pair.1,
+                level
+            )?;
+        }
+
+        Ok(node)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::halo2::{
+        dev::MockProver,
+        pasta::Fp,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+    use std::marker::PhantomData;
+    use pasta_curves::pallas;
+
+    use crate::utils::{UtilitiesInstructions, NumericCell, Numeric, Var, CellValue, from_cell_vale_to_numeric};
+    use super::{SparseMerkleChip, SparseMerkleConfig, NonMembershipProof};
+    use crate::poseidon::{P128Pow5T3};
+    use crate::gadget::poseidon::{Pow5T3Chip as PoseidonChip};
+    use crate::gadget::merkle::MerkleChip;
+    use crate::gadget::range::RangeCheckChip;
+
+    use crate::merkle::sparse::SparseMerkleTree;
+
+    const DEPTH: usize = 8;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        advice: [Column<Advice>; 4],
+        instance: Column<Instance>,
+        sparse_config: SparseMerkleConfig
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        key: Option<Fp>,
+        leaf: Option<Fp>,
+        siblings: [Option<Fp>; DEPTH],
+        // root a dishonest prover assigns instead of the computed one
+        forged_root: Option<Fp>,
+    }
+
+    impl UtilitiesInstructions<pallas::Base> for Circuit {
+        type Var = NumericCell<pallas::Base>;
+    }
+
+    impl plonk::Circuit<pallas::Base> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column()
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            for advice in advice.iter() {
+                meta.enable_equality((*advice).into());
+            }
+
+            let rc_a = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+            let rc_b = [
+                meta.fixed_column(),
+                meta.fixed_column(),
+                meta.fixed_column(),
+            ];
+
+            meta.enable_constant(rc_b[0]);
+
+            let poseidon_config = PoseidonChip::configure(meta, P128Pow5T3, advice[0..3].try_into().unwrap(), advice[3], rc_a, rc_b);
+            let merkle_config = MerkleChip::<pallas::Base>::configure(meta, advice[0..3].try_into().unwrap(), poseidon_config);
+            let range_config = RangeCheckChip::configure(meta, advice[0..3].try_into().unwrap());
+
+            let sparse_config = SparseMerkleChip::configure(meta, advice[0..3].try_into().unwrap(), merkle_config, range_config);
+
+            Config {
+                advice,
+                instance,
+                sparse_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<pallas::Base>,
+        ) -> Result<(), Error> {
+            let key = self.load_private(
+                layouter.namespace(|| "witness key"),
+                config.advice[0],
+                self.key,
+            )?;
+
+            let proof = NonMembershipProof {
+                chip: SparseMerkleChip::construct(config.sparse_config.clone()),
+                leaf: self.leaf,
+                siblings: self.siblings,
+                _marker: PhantomData::<pallas::Base>
+            };
+
+            let mut root = proof.calculate_root(layouter.namespace(|| "sparse root"), key)?;
+            if let Some(forged) = self.forged_root {
+                root = from_cell_vale_to_numeric(
+                    layouter.namespace(|| "forge root"),
+                    config.advice[0],
+                    CellValue::new(root.cell(), Some(forged)),
+                )?;
+            }
+            self.expose_public(layouter.namespace(|| "expose root"), config.instance, root, 0)?;
+
+            Ok(())
+        }
+    }
+
+    fn verifies(circuit: &Circuit, root: Fp) -> bool {
+        let k = 10;
+        let prover = MockProver::run(k, circuit, vec![vec![root]]).unwrap();
+        prover.verify().is_ok()
+    }
+
+    fn circuit(key: Fp, leaf: Fp, siblings: &[Fp]) -> Circuit {
+        let siblings: Vec<Option<Fp>> = siblings.iter().map(|sibling| Some(*sibling)).collect();
+        Circuit {
+            key: Some(key),
+            leaf: Some(leaf),
+            siblings: siblings.try_into().expect("siblings with incorrect length"),
+            forged_root: None,
+        }
+    }
+
+    /// Whether the circuit verifies when the prover puts `root` in the cell of the root.
+    fn verifies_forged(circuit: Circuit, root: Fp) -> bool {
+        verifies(&Circuit { forged_root: Some(root), ..circuit }, root)
+    }
+
+    fn run(tree: &SparseMerkleTree, key: Fp, root: Fp) -> bool {
+        let proof = tree.prove(key).unwrap();
+        verifies(&circuit(key, proof.leaf, &proof.siblings), root)
+    }
+
+    #[test]
+    fn non_membership_test() {
+        let mut tree = SparseMerkleTree::new(DEPTH).unwrap();
+        tree.insert(Fp::from(3)).unwrap();
+        tree.insert(Fp::from(100)).unwrap();
+        tree.insert(Fp::from(200)).unwrap();
+
+        // empty slot
+        assert!(run(&tree, Fp::from(5), tree.root()));
+
+        assert!(!run(&tree, Fp::from(5), tree.root() + Fp::one()));
+    }
+
+    #[test]
+    fn member_test() {
+        let mut tree = SparseMerkleTree::new(DEPTH).unwrap();
+        tree.insert(Fp::from(3)).unwrap();
+        tree.insert(Fp::from(100)).unwrap();
+
+        assert!(!run(&tree, Fp::from(3), tree.root()));
+        assert!(!run(&tree, Fp::from(100), tree.root()));
+
+        // a key sharing the low 8 bits of 3 cannot use the slot of 3
+        let proof = tree.prove(Fp::from(3)).unwrap();
+        assert!(!verifies(&circuit(Fp::from(3 + 256), proof.leaf, &proof.siblings), tree.root()));
+    }
+
+    #[test]
+    fn forged_path() {
+        let mut tree = SparseMerkleTree::new(DEPTH).unwrap();
+        tree.insert(Fp::from(3)).unwrap();
+        tree.insert(Fp::from(100)).unwrap();
+        let root = tree.root();
+
+        // forging the root with its own value changes nothing
+        let proof = tree.prove(Fp::from(5)).unwrap();
+        assert!(verifies_forged(circuit(Fp::from(5), proof.leaf, &proof.siblings), root));
+
+        // the banned key 3 with an empty leaf and made up siblings, claiming the real root
+        let siblings: Vec<Fp> = (0..DEPTH as u64).map(Fp::from).collect();
+        assert!(!verifies(&circuit(Fp::from(3), Fp::zero(), &siblings), root));
+        assert!(!verifies_forged(circuit(Fp::from(3), Fp::zero(), &siblings), root));
+    }
+}
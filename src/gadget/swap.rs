@@ -22,6 +22,14 @@ pub trait SwapInstruction<F: FieldExt>: UtilitiesInstructions<F> {
         pair: (Self::Var, Option<F>),
         swap: Option<bool>,
     ) -> Result<(Self::Var, Self::Var), Error>;
+
+    /// Like [`SwapInstruction::swap`], with the swap decided by an already assigned bit.
+    fn swap_with_bit(
+        &self,
+        layouter: impl Layouter<F>,
+        pair: (Self::Var, Option<F>),
+        bit: Self::Var,
+    ) -> Result<(Self::Var, Self::Var), Error>;
 }
 
 #[derive(Clone, Debug)]
@@ -64,6 +72,7 @@ impl<F: FieldExt> SwapChip<F> {
         let left = advices[0];
         // we must enable equality so that copy can work
         meta.enable_equality(left.into());
+        meta.enable_equality(advices[2].into());
 
         let q_swap = meta.selector();
 
@@ -115,10 +124,33 @@ impl<F: FieldExt> SwapChip<F> {
 impl<F: FieldExt> SwapInstruction<F> for SwapChip<F> {
     fn swap(
         &self, 
-        mut layouter: impl Layouter<F>,
+        layouter: impl Layouter<F>,
         pair: (Self::Var, Option<F>),
         swap: Option<bool>
     ) -> Result<(Self::Var, Self::Var), Error> {
+        self.assign_swap(layouter, pair, swap, None)
+    }
+
+    fn swap_with_bit(
+        &self,
+        layouter: impl Layouter<F>,
+        pair: (Self::Var, Option<F>),
+        bit: Self::Var,
+    ) -> Result<(Self::Var, Self::Var), Error> {
+        let swap = bit.value().map(|bit| bit == F::one());
+        self.assign_swap(layouter, pair, swap, Some(bit))
+    }
+}
+
+impl<F: FieldExt> SwapChip<F> {
+    /// Assigns the swap region, copying `bit` into the swap column when given.
+    fn assign_swap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        pair: (NumericCell<F>, Option<F>),
+        swap: Option<bool>,
+        bit: Option<NumericCell<F>>,
+    ) -> Result<(NumericCell<F>, NumericCell<F>), Error> {
         let config = self.config();
 
         layouter.assign_region(
@@ -140,13 +172,20 @@ impl<F: FieldExt> SwapInstruction<F> for SwapChip<F> {
                     NumericCell::new(cell)
                 };
 
-                let swap_value = swap.map(|swap| F::from(swap as u64));
-                region.assign_advice(
-                    || "witness swap",
-                    config.should_swap,
-                    row_offset,
-                    || swap_value.ok_or(Error::Synthesis),
-                )?;
+                match &bit {
+                    Some(bit) => {
+                        bit.copy(|| "copy swap", &mut region, config.should_swap, row_offset)?;
+                    }
+                    None => {
+                        let swap_value = swap.map(|swap| F::from(swap as u64));
+                        region.assign_advice(
+                            || "witness swap",
+                            config.should_swap,
+                            row_offset,
+                            || swap_value.ok_or(Error::Synthesis),
+                        )?;
+                    }
+                }
 
                 row_offset += 1;
 
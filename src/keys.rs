@@ -3,50 +3,270 @@ use crate::halo2::{
     plonk,
 };
 
-use pasta_curves::{
-    vesta
-};
+use pasta_curves::vesta;
+
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
 
 use crate::circuit::{Circuit};
+use crate::error::Error;
+
+/// Magic bytes at the start of every serialized key.
+const KEY_MAGIC: &[u8; 4] = b"RLNK";
+/// Version of the on-disk key format.
+pub const KEY_FORMAT_VERSION: u8 = 3;
+
+/// Largest `k` accepted when reading a key, so a crafted header cannot make the
+/// reader allocate and process params of an arbitrary size.
+pub const MAX_K: u32 = 24;
 
+const PREFIX_RLN_VERIFYING_KEY: &[u8; 17] = b"rln_verifying_key";
 
 #[derive(Debug)]
-pub struct VerifyingKey {
+pub struct VerifyingKey<const DEPTH: usize> {
     pub params: crate::halo2::poly::commitment::Params<vesta::Affine>,
     pub vk: plonk::VerifyingKey<vesta::Affine>,
+    /// Computed once when the key is built or read.
+    fingerprint: [u8; 32],
 }
 
 #[derive(Debug)]
-pub struct ProvingKey {
+pub struct ProvingKey<const DEPTH: usize> {
     pub params: params<vesta::Affine>,
     pub pk: plonk::ProvingKey<vesta::Affine>,
+    /// Computed once when the key is built or read.
+    fingerprint: [u8; 32],
+}
+
+/// Writes the key header followed by `params` and `vk`.
+fn write_key<W: io::Write, const DEPTH: usize>(
+    writer: &mut W,
+    params: &params<vesta::Affine>,
+    vk: &plonk::VerifyingKey<vesta::Affine>,
+) -> Result<(), Error> {
+    // halo2 encodes params starting with their `k`, which is repeated in the header
+    let mut encoded_params = vec![];
+    params.write(&mut encoded_params)?;
+
+    writer.write_all(KEY_MAGIC)?;
+    writer.write_all(&[KEY_FORMAT_VERSION])?;
+    writer.write_all(&encoded_params[..4])?;
+    writer.write_all(&(DEPTH as u32).to_le_bytes())?;
+    writer.write_all(&encoded_params)?;
+    vk.write(writer)?;
+    Ok(())
+}
+
+/// Hash of the serialized key, the same for a proving key and its verifying key.
+fn key_fingerprint<const DEPTH: usize>(
+    params: &params<vesta::Affine>,
+    vk: &plonk::VerifyingKey<vesta::Affine>,
+) -> [u8; 32] {
+    let mut encoded = vec![];
+    write_key::<_, DEPTH>(&mut encoded, params, vk).expect("writing to a vec does not fail");
+
+    let mut hasher = Sha256::new();
+    hasher.update(PREFIX_RLN_VERIFYING_KEY);
+    hasher.update(&encoded);
+    hasher.finalize().into()
+}
+
+/// Reads the key header, `params` and `vk`, checking them against the compiled `Circuit`.
+///
+/// The verifying key is regenerated from the stored params and compared byte for byte
+/// with the stored one, so a key for another circuit shape or `k` is rejected.
+fn read_key<R: io::Read, const DEPTH: usize>(
+    reader: &mut R,
+) -> Result<(params<vesta::Affine>, plonk::VerifyingKey<vesta::Affine>), Error> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != KEY_MAGIC {
+        return Err(Error::InvalidKey("not an rln key"));
+    }
+
+    let mut version = [0u8; 1];
+    reader.read_exact(&mut version)?;
+    if version[0] != KEY_FORMAT_VERSION {
+        return Err(Error::InvalidKey("unsupported key format version"));
+    }
+
+    let mut k = [0u8; 4];
+    reader.read_exact(&mut k)?;
+    if u32::from_le_bytes(k) > MAX_K {
+        return Err(Error::InvalidKey("k is larger than MAX_K"));
+    }
+
+    let mut depth = [0u8; 4];
+    reader.read_exact(&mut depth)?;
+    if u32::from_le_bytes(depth) as usize != DEPTH {
+        return Err(Error::InvalidKey("key was built for a different tree depth"));
+    }
+
+    let mut params_k = [0u8; 4];
+    reader.read_exact(&mut params_k)?;
+    if params_k != k {
+        return Err(Error::InvalidKey("params do not match key header k"));
+    }
+
+    let params = params::read(&mut (&params_k[..]).chain(&mut *reader))?;
+
+    let circuit: Circuit<DEPTH> = Default::default();
+    let vk = plonk::keygen_vk(&params, &circuit)
+        .map_err(|_| Error::InvalidKey("k is too small for the circuit"))?;
+
+    let mut expected_vk = vec![];
+    vk.write(&mut expected_vk)?;
+    let mut stored_vk = vec![0u8; expected_vk.len()];
+    reader.read_exact(&mut stored_vk)?;
+    if stored_vk != expected_vk {
+        return Err(Error::InvalidKey("key was built for a different circuit"));
+    }
+
+    Ok((params, vk))
 }
 
-impl VerifyingKey {
+impl<const DEPTH: usize> VerifyingKey<DEPTH> {
     /// Builds the verifying key.
     pub fn build(k: u32) -> Self {
         let params = crate::halo2::poly::commitment::Params::new(k);
-        let circuit: Circuit = Default::default();
+        let circuit: Circuit<DEPTH> = Default::default();
 
         let vk = plonk::keygen_vk(&params, &circuit).unwrap();
+        let fingerprint = key_fingerprint::<DEPTH>(&params, &vk);
+
+        VerifyingKey { params, vk, fingerprint }
+    }
+
+    /// Writes the verifying key together with its params in the versioned key format.
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_key::<_, DEPTH>(writer, &self.params, &self.vk)
+    }
 
-        VerifyingKey { params, vk }
+    /// Reads a verifying key written by [`VerifyingKey::write`] or [`ProvingKey::write`].
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        let (params, vk) = read_key::<_, DEPTH>(reader)?;
+        let fingerprint = key_fingerprint::<DEPTH>(&params, &vk);
+        Ok(VerifyingKey { params, vk, fingerprint })
     }
 
-    pub fn export<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        self.vk.write(writer)
+    /// Identifies the key and its params, see [`crate::bundle::RlnProofBundle`].
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
+    }
+
+    /// Alias of [`VerifyingKey::write`].
+    pub fn export<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        self.write(writer)
+    }
+
+    /// Alias of [`VerifyingKey::read`].
+    pub fn import<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        Self::read(reader)
     }
 }
 
-impl ProvingKey {
+impl<const DEPTH: usize> ProvingKey<DEPTH> {
     /// Builds the proving key.
     pub fn build(k: u32) -> Self {
         let params = crate::halo2::poly::commitment::Params::new(k);
-        let circuit: Circuit = Default::default();
+        let circuit: Circuit<DEPTH> = Default::default();
 
         let vk = plonk::keygen_vk(&params, &circuit).unwrap();
+        let fingerprint = key_fingerprint::<DEPTH>(&params, &vk);
         let pk = plonk::keygen_pk(&params, vk, &circuit).unwrap();
 
-        ProvingKey { params, pk }
+        ProvingKey { params, pk, fingerprint }
+    }
+
+    /// Writes the proving key in the versioned key format.
+    ///
+    /// halo2 cannot serialize the proving key itself, so only the params and the
+    /// verifying key are stored: reading saves generating the params, not keygen.
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_key::<_, DEPTH>(writer, &self.params, self.pk.get_vk())
+    }
+
+    /// Fingerprint of the verifying key of this proving key.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
     }
-}
\ No newline at end of file
+
+    /// Reads a proving key written by [`ProvingKey::write`] or [`VerifyingKey::write`].
+    ///
+    /// Runs `keygen_vk` and `keygen_pk` over the stored params, so this costs as much
+    /// as [`ProvingKey::build`] minus the params generation.
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        let (params, vk) = read_key::<_, DEPTH>(reader)?;
+        let fingerprint = key_fingerprint::<DEPTH>(&params, &vk);
+        let circuit: Circuit<DEPTH> = Default::default();
+        let pk = plonk::keygen_pk(&params, vk, &circuit)
+            .map_err(|_| Error::InvalidKey("failed to rebuild proving key"))?;
+
+        Ok(ProvingKey { params, pk, fingerprint })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{key_fingerprint, ProvingKey, VerifyingKey, KEY_FORMAT_VERSION, MAX_K};
+    use crate::error::Error;
+
+    const K: u32 = 11;
+    const DEPTH: usize = 16;
+
+    #[test]
+    fn round_trip() {
+        let pk = ProvingKey::<DEPTH>::build(K);
+
+        let mut encoded = vec![];
+        pk.write(&mut encoded).unwrap();
+
+        let vk = VerifyingKey::<DEPTH>::read(&mut &encoded[..]).unwrap();
+        let mut reencoded = vec![];
+        vk.export(&mut reencoded).unwrap();
+        assert!(encoded == reencoded);
+        assert_eq!(vk.fingerprint(), pk.fingerprint());
+        assert_eq!(vk.fingerprint(), key_fingerprint::<DEPTH>(&vk.params, &vk.vk));
+
+        let read = ProvingKey::<DEPTH>::read(&mut &encoded[..]).unwrap();
+        let mut reencoded = vec![];
+        read.write(&mut reencoded).unwrap();
+        assert!(encoded == reencoded);
+        assert_eq!(read.fingerprint(), pk.fingerprint());
+    }
+
+    #[test]
+    fn reject_mismatch() {
+        let vk = VerifyingKey::<DEPTH>::build(K);
+        let mut encoded = vec![];
+        vk.write(&mut encoded).unwrap();
+
+        let mut wrong_version = encoded.clone();
+        wrong_version[4] = KEY_FORMAT_VERSION + 1;
+        assert!(matches!(VerifyingKey::<DEPTH>::read(&mut &wrong_version[..]), Err(Error::InvalidKey(_))));
+
+        let mut wrong_k = encoded.clone();
+        wrong_k[5] += 1;
+        assert!(VerifyingKey::<DEPTH>::read(&mut &wrong_k[..]).is_err());
+
+        // k is rejected before any params are read
+        let mut huge_k = encoded.clone();
+        huge_k[5..9].copy_from_slice(&(MAX_K + 1).to_le_bytes());
+        huge_k[13..17].copy_from_slice(&(MAX_K + 1).to_le_bytes());
+        assert!(matches!(VerifyingKey::<DEPTH>::read(&mut &huge_k[..]), Err(Error::InvalidKey(_))));
+        assert!(matches!(VerifyingKey::<DEPTH>::read(&mut &huge_k[..9]), Err(Error::InvalidKey(_))));
+
+        assert!(matches!(VerifyingKey::<30>::read(&mut &encoded[..]), Err(Error::InvalidKey(_))));
+
+        assert!(matches!(VerifyingKey::<DEPTH>::read(&mut &encoded[..8]), Err(Error::Io(_))));
+
+        let mut wrong_shape = encoded.clone();
+        *wrong_shape.last_mut().unwrap() ^= 1;
+        assert!(matches!(VerifyingKey::<DEPTH>::read(&mut &wrong_shape[..]), Err(Error::InvalidKey(_))));
+
+        let too_small = VerifyingKey::<DEPTH> { params: crate::halo2::poly::commitment::Params::new(2), vk: vk.vk, fingerprint: [0u8; 32] };
+        let mut encoded = vec![];
+        too_small.write(&mut encoded).unwrap();
+        assert!(VerifyingKey::<DEPTH>::read(&mut &encoded[..]).is_err());
+    }
+}
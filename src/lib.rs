@@ -18,7 +18,10 @@ pub mod hash_to_field;
 pub mod merkle;
 pub mod client;
 pub mod proof;
+pub mod bundle;
+pub mod transcript;
 pub mod keys;
+pub mod error;
 
 // #[cfg(target_arch = "wasm32")]
 // pub mod build;
\ No newline at end of file
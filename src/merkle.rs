@@ -1,44 +1,169 @@
-use crate::halo2::arithmetic::FieldExt;
 use crate::halo2::pasta::Fp;
 use std::iter;
-use pasta_curves::pallas;
-use crate::poseidon::{self, P128Pow5T3, ConstantLength};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::marker::PhantomData;
+use crate::error::Error;
+
+pub mod hasher;
+pub mod proof;
+pub mod quaternary;
+pub mod sparse;
+pub mod store;
+pub mod sync;
+pub mod witness;
+
+use hasher::{PoseidonHasher, TreeHasher, TreeNode};
+use proof::MerkleProof;
+use store::{Checkpoint, MemoryStore, NodeStore};
+use witness::Insertion;
+
+/// Deepest tree supported by [`IncrementalTree`].
+pub const MAX_DEPTH: usize = 32;
+
+/// Number of recent roots an [`IncrementalTree`] accepts by default.
+pub const DEFAULT_ROOT_HISTORY_SIZE: usize = 100;
+
+/// Decides which Merkle roots a verifier accepts proofs for.
+pub trait RootPolicy {
+    fn is_acceptable_root(&self, root: &Fp) -> bool;
+}
 
-pub struct IncrementalTree {
-    root: Fp,
-    zeroes: Vec<Fp>,
-    nodes: Vec<Vec<Fp>>,
-    depth: usize,
-    position: usize,
+/// Only this exact root is accepted.
+impl RootPolicy for Fp {
+    fn is_acceptable_root(&self, root: &Fp) -> bool {
+        self == root
+    }
+}
+
+/// Bounded window of the most recent roots of a tree.
+///
+/// Each root is tagged with the number of leaves inserted when it became current.
+/// Once the window is full the oldest root is dropped.
+#[derive(Clone, Debug)]
+pub struct RootHistory<N = Fp> {
+    roots: VecDeque<(N, usize)>,
+    size: usize,
 }
 
-trait Hash<F: FieldExt> {
-    fn hash_layer(
-        left: F, 
-        right: F
-    ) -> F;
+impl<N: TreeNode> RootHistory<N> {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "root history must hold at least one root");
+        RootHistory { roots: VecDeque::with_capacity(size), size }
+    }
+
+    /// Records `root` as the current root, dropping the oldest one if the window is full.
+    pub fn push(&mut self, root: N, leaves: usize) {
+        if self.roots.len() == self.size {
+            self.roots.pop_front();
+        }
+        self.roots.push_back((root, leaves));
+    }
+
+    /// Drops every root, for when older roots must no longer be accepted.
+    pub fn clear(&mut self) {
+        self.roots.clear();
+    }
+
+    /// Drops the roots tagged with more than `index` leaves, the ones that hold the
+    /// leaf at `index`. Older roots, from before that leaf was inserted, stay accepted.
+    pub fn revoke(&mut self, index: usize) {
+        self.roots.retain(|(_, leaves)| *leaves <= index);
+    }
+
+    /// Returns the leaf count `root` was tagged with, if it is still in the window.
+    pub fn get(&self, root: &N) -> Option<usize> {
+        self.roots.iter().rev().find(|(r, _)| r == root).map(|(_, leaves)| *leaves)
+    }
+
+    /// Roots in the window with their tags, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &(N, usize)> {
+        self.roots.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
 }
 
-impl Hash<pallas::Base> for IncrementalTree {
-    fn hash_layer(
-        left: pallas::Base,
-        right: pallas::Base
-    ) -> pallas::Base {
-        poseidon::Hash::init(P128Pow5T3, ConstantLength::<2>).hash([left, right])
+impl RootPolicy for RootHistory<Fp> {
+    fn is_acceptable_root(&self, root: &Fp) -> bool {
+        self.get(root).is_some()
     }
 }
 
+/// Any root still in the tree's history is accepted.
+impl<H: TreeHasher<Node = Fp>, S: NodeStore<Fp>> RootPolicy for IncrementalTree<H, S> {
+    fn is_acceptable_root(&self, root: &Fp) -> bool {
+        self.history.is_acceptable_root(root)
+    }
+}
+
+/// Root above the two nodes of level `depth - 1` of `store`.
+fn stored_root<H: TreeHasher, S: NodeStore<H::Node>>(store: &S, zeroes: &[H::Node], depth: usize) -> H::Node {
+    match depth {
+        0 => zeroes[0],
+        _ => {
+            let level = depth - 1;
+            let left = store.get(level, 0).unwrap_or(zeroes[level]);
+            let right = store.get(level, 1).unwrap_or(zeroes[level]);
+            H::hash_layer(left, right)
+        }
+    }
+}
+
+/// Merkle tree filled from the left, hashed with `H` and with its nodes kept in `S`.
+pub struct IncrementalTree<H: TreeHasher = PoseidonHasher, S: NodeStore<H::Node> = MemoryStore<<H as TreeHasher>::Node>> {
+    root: H::Node,
+    zeroes: Vec<H::Node>,
+    store: S,
+    depth: usize,
+    position: usize,
+    // encoding of each leaf mapped to all its positions
+    indices: HashMap<[u8; 32], BTreeSet<usize>>,
+    history: RootHistory<H::Node>,
+    _marker: PhantomData<H>,
+}
+
 impl IncrementalTree {
+    /// Creates an empty in-memory tree, panicking if `depth` is above [`MAX_DEPTH`].
     pub fn new(zero_value: Fp, depth: usize) -> Self {
+        Self::try_new(zero_value, depth).expect("MAX DEPTH EXCEEDED")
+    }
 
-        if depth > 32 { panic!("MAX DEPTH EXCEEDED") }
+    /// Creates an empty in-memory tree whose empty slots hold `zero_value`.
+    pub fn try_new(zero_value: Fp, depth: usize) -> Result<Self, Error> {
+        Self::open(MemoryStore::new(), zero_value, depth)
+    }
+}
+
+impl<H: TreeHasher, S: NodeStore<H::Node>> IncrementalTree<H, S> {
+    /// Opens the tree kept in `store`, starting an empty one if the store is empty.
+    ///
+    /// Every stored parent is checked against its children, so the root of the
+    /// reopened tree follows from the stored leaves, and it must match the last
+    /// checkpoint of the store. Parents whose children all come from a snapshot of the
+    /// store, see [`NodeStore::snapshot_len`], were checked before it was written and
+    /// are not hashed again. Leaves written after that checkpoint are only kept
+    /// by stores that do not discard them.
+    pub fn open(store: S, zero_value: H::Node, depth: usize) -> Result<Self, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::DepthTooLarge(depth));
+        }
 
-        let zeroes: Vec<Fp> = {
+        let zeroes: Vec<H::Node> = {
             iter::empty()
             .chain(Some(zero_value))
             .chain(
                 (0..depth).scan(zero_value, |zero, _level| {
-                    *zero = IncrementalTree::hash_layer(*zero, *zero);
+                    *zero = H::hash_layer(*zero, *zero);
                     Some(*zero)
                 })
             )
@@ -47,96 +172,345 @@ impl IncrementalTree {
 
         assert_eq!(zeroes.len(), depth + 1);
 
-        IncrementalTree {
-            root: *zeroes.last().unwrap(),
+        let position = if depth == 0 { 0 } else { store.len(0) };
+        for level in 1..depth {
+            if store.len(level) != store.len(level - 1).div_ceil(2) {
+                return Err(Error::CorruptStore("missing or extra nodes"));
+            }
+            let checked = store.snapshot_len(level).min(store.snapshot_len(level - 1) / 2);
+            for parent in checked..store.len(level) {
+                let left = store.get(level - 1, 2 * parent).unwrap_or(zeroes[level - 1]);
+                let right = store.get(level - 1, 2 * parent + 1).unwrap_or(zeroes[level - 1]);
+                if store.get(level, parent) != Some(H::hash_layer(left, right)) {
+                    return Err(Error::CorruptStore("stored node does not match its children"));
+                }
+            }
+        }
+        if depth > 0 && store.len(depth - 1) > 2 {
+            return Err(Error::CorruptStore("missing or extra nodes"));
+        }
+
+        let root = stored_root::<H, S>(&store, &zeroes, depth);
+
+        if let Some(checkpoint) = store.last_checkpoint() {
+            if checkpoint.depth != depth {
+                return Err(Error::CorruptStore("store holds a tree of another depth"));
+            }
+            if checkpoint.leaves != position || checkpoint.root != root {
+                return Err(Error::CorruptStore("stored root does not match the stored nodes"));
+            }
+        }
+
+        let mut indices: HashMap<[u8; 32], BTreeSet<usize>> = HashMap::new();
+        for index in 0..position {
+            let leaf = store.get(0, index).ok_or(Error::CorruptStore("missing leaf"))?;
+            if leaf != zeroes[0] {
+                indices.entry(leaf.to_bytes()).or_default().insert(index);
+            }
+        }
+
+        let mut history = RootHistory::new(DEFAULT_ROOT_HISTORY_SIZE);
+        history.push(root, position);
+
+        Ok(IncrementalTree {
+            root,
             zeroes,
-            nodes: vec![Vec::new(); depth],
+            store,
             depth,
-            position: 0,
+            position,
+            indices,
+            history,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Records the current root in the store so the tree can be reopened at it.
+    pub fn checkpoint(&mut self) -> Result<(), Error> {
+        self.store.checkpoint(&Checkpoint {
+            depth: self.depth,
+            leaves: self.position,
+            root: self.root,
+        })
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Appends `leaf`, panicking on the zero value or a full tree.
+    pub fn insert(&mut self, leaf: H::Node) {
+        if let Err(err) = self.try_insert(leaf) {
+            panic!("{}", err);
         }
     }
 
-    pub fn insert(&mut self, leaf: Fp) {
+    /// Appends `leaf` at the next free position.
+    pub fn try_insert(&mut self, leaf: H::Node) -> Result<(), Error> {
         if leaf == self.zeroes[0] {
-            panic!("Leaf cannot be equal to zero value");
+            return Err(Error::ZeroLeaf);
+        }
+
+        if self.is_full() {
+            return Err(Error::TreeFull);
         }
 
-        if self.nodes.len() >= usize::pow(2, self.depth.try_into().unwrap()) {
-            panic!("Tree is full");
+        let mut node = leaf;
+        let mut index = self.position;
+        for level in 0..self.depth {
+            self.store.set(level, index, node)?;
+
+            node = if (index % 2) == 1 {
+                let left = self.store.get(level, index - 1).ok_or(Error::CorruptStore("missing node"))?;
+                H::hash_layer(left, node)
+            } else {
+                H::hash_layer(node, self.zeroes[level])
+            };
+            index >>= 1;
         }
 
-        // let hasher = Hash::init(P128Pow5T3, ConstantLength<2>);
-        let IncrementalTree { root, zeroes, nodes, depth, position } = self;
+        self.indices.entry(leaf.to_bytes()).or_default().insert(self.position);
+        self.position += 1;
+        self.root = node;
+        self.history.push(self.root, self.position);
+        Ok(())
+    }
+
+    /// Appends all `leaves` at the next free positions.
+    ///
+    /// Every internal node above the new leaves is hashed once, so a batch of `n`
+    /// leaves costs about `n + depth` hashes instead of `n * depth`. Nothing is
+    /// inserted if a leaf is the zero value or the batch does not fit.
+    pub fn insert_batch(&mut self, leaves: &[H::Node]) -> Result<(), Error> {
+        if leaves.is_empty() {
+            return Ok(());
+        }
 
-        let mut append_leaf = |node, level, index| {
-            let level = level as usize;
+        if leaves.iter().any(|leaf| *leaf == self.zeroes[0]) {
+            return Err(Error::ZeroLeaf);
+        }
 
-            if nodes[level].len() > index { nodes[level][index] = node; } 
-            else { nodes[level].push(node); }
+        if (self.position + leaves.len()) as u64 > self.capacity() {
+            return Err(Error::TreeFull);
+        }
 
-            if (index % 2) == 1 { 
-                IncrementalTree::hash_layer(nodes[level][index - 1], node)
-            } else { 
-                IncrementalTree::hash_layer(node, zeroes[level])
+        let start = self.position;
+        for (offset, leaf) in leaves.iter().enumerate() {
+            self.store.set(0, start + offset, *leaf)?;
+            self.indices.entry(leaf.to_bytes()).or_default().insert(start + offset);
+        }
+
+        // changed nodes on the current level are within [first, last]
+        let (mut first, mut last) = (start, start + leaves.len() - 1);
+        let mut root = self.root;
+        for level in 0..self.depth {
+            for parent in first / 2..=last / 2 {
+                let left = self.store.get(level, 2 * parent).unwrap_or(self.zeroes[level]);
+                let right = self.store.get(level, 2 * parent + 1).unwrap_or(self.zeroes[level]);
+                let node = H::hash_layer(left, right);
+
+                if level + 1 < self.depth {
+                    self.store.set(level + 1, parent, node)?;
+                } else {
+                    root = node;
+                }
             }
-        };
-        
-        let mut node = leaf;
-        let mut index = *position;
-        for level in 0..*depth {
-            node = append_leaf(node, level, index);
-            index = (index as f64 / 2 as f64).floor() as usize;
+
+            first /= 2;
+            last /= 2;
         }
 
-        *position += 1;
-        *root = node;
-        ()
+        self.position += leaves.len();
+        self.root = root;
+        self.history.push(self.root, self.position);
+        Ok(())
     }
 
-    pub fn witness(&mut self, leaf: Fp) -> (Vec<Fp>, Vec<bool>) {
-        let IncrementalTree { zeroes, nodes, depth, .. } = self;
+    /// Replaces the leaf at `index` and recomputes its path up to the root.
+    ///
+    /// Roots that hold the old leaf are dropped from the root history, roots from
+    /// before it was inserted are still accepted.
+    pub fn update(&mut self, index: usize, leaf: H::Node) -> Result<(), Error> {
+        if leaf == self.zeroes[0] {
+            return Err(Error::ZeroLeaf);
+        }
 
-        let index = nodes[0].iter().position(|&el| el == leaf );
-        if index.is_none() { panic!("No such leaf"); }
+        self.set_leaf(index, leaf)
+    }
 
-        let mut index = index.unwrap();
+    /// Removes the leaf at `index` by resetting its slot to the zero value.
+    ///
+    /// Positions of the other leaves do not change, so their paths stay valid
+    /// against the new root once re-witnessed. As with [`Self::update`], roots that
+    /// hold the removed leaf are no longer accepted.
+    pub fn delete(&mut self, index: usize) -> Result<(), Error> {
+        let zero_value = self.zeroes[0];
+        self.set_leaf(index, zero_value)
+    }
 
-        let mut siblings = vec![zeroes[0]; depth.clone()];
-        let mut pos = vec![false; depth.clone()];
+    fn set_leaf(&mut self, index: usize, leaf: H::Node) -> Result<(), Error> {
+        if index >= self.position {
+            return Err(Error::IndexOutOfBounds(index));
+        }
 
-        let mut sibling_path = |level, index| {
-            let level = level as usize;
+        let old_leaf = self.store.get(0, index).ok_or(Error::CorruptStore("missing leaf"))?;
+        if let Some(positions) = self.indices.get_mut(&old_leaf.to_bytes()) {
+            positions.remove(&index);
+            if positions.is_empty() {
+                self.indices.remove(&old_leaf.to_bytes());
+            }
+        }
+        if leaf != self.zeroes[0] {
+            self.indices.entry(leaf.to_bytes()).or_default().insert(index);
+        }
 
-            if (index % 2) == 1 {
-                siblings[level] = nodes[level][index - 1];
-                pos[level] = true;
+        let leaf_index = index;
+        let mut node = leaf;
+        let mut index = index;
+        for level in 0..self.depth {
+            self.store.set(level, index, node)?;
+
+            let sibling = self.store.get(level, index ^ 1).unwrap_or(self.zeroes[level]);
+            node = if index & 1 == 1 {
+                H::hash_layer(sibling, node)
             } else {
-                siblings[level] = zeroes[level];
+                H::hash_layer(node, sibling)
+            };
+            index >>= 1;
+        }
+
+        // roots holding the replaced leaf would still let its owner prove membership
+        self.root = node;
+        self.history.revoke(leaf_index);
+        self.history.push(self.root, self.position);
+        Ok(())
+    }
+
+    /// Drops the leaves from position `leaves` on, as if they were never inserted.
+    ///
+    /// If any leaf is dropped, the root history only holds the new root afterwards,
+    /// as after an update.
+    pub fn truncate(&mut self, leaves: usize) -> Result<(), Error> {
+        if leaves > self.position {
+            return Err(Error::IndexOutOfBounds(leaves));
+        }
+        if leaves == self.position {
+            return Ok(());
+        }
+
+        for index in leaves..self.position {
+            let leaf = self.store.get(0, index).ok_or(Error::CorruptStore("missing leaf"))?;
+            if let Some(positions) = self.indices.get_mut(&leaf.to_bytes()) {
+                positions.remove(&index);
+                if positions.is_empty() {
+                    self.indices.remove(&leaf.to_bytes());
+                }
             }
-        };
+        }
 
-        for level in 0..*depth {
-            sibling_path(level, index);
-            index = (index as f64 / 2 as f64).floor() as usize;
+        let mut len = leaves;
+        for level in 0..self.depth {
+            self.store.truncate(level, len)?;
+
+            // the last node may have lost its right child
+            if level > 0 && len > 0 {
+                let parent = len - 1;
+                let left = self.store.get(level - 1, 2 * parent).unwrap_or(self.zeroes[level - 1]);
+                let right = self.store.get(level - 1, 2 * parent + 1).unwrap_or(self.zeroes[level - 1]);
+                self.store.set(level, parent, H::hash_layer(left, right))?;
+            }
+            len = len.div_ceil(2);
+        }
+
+        self.position = leaves;
+        self.root = stored_root::<H, S>(&self.store, &self.zeroes, self.depth);
+        self.history.clear();
+        self.history.push(self.root, self.position);
+        Ok(())
+    }
+
+    /// Replaces the root history.
+    pub(crate) fn set_root_history(&mut self, history: RootHistory<H::Node>) {
+        self.history = history;
+    }
+
+    /// Returns the siblings and path bits of `leaf`.
+    ///
+    /// If the same leaf was inserted several times, the path of the first one is returned.
+    pub fn witness(&self, leaf: H::Node) -> Result<(Vec<H::Node>, Vec<bool>), Error> {
+        let index = self.first_index(&leaf).ok_or(Error::UnknownLeaf)?;
+        self.witness_at(index)
+    }
+
+    /// Returns the siblings and path bits of the leaf at `index`.
+    ///
+    /// A path bit is `true` when the node on that level is a right child.
+    pub fn witness_at(&self, index: usize) -> Result<(Vec<H::Node>, Vec<bool>), Error> {
+        if index >= self.position {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+
+        let mut siblings = Vec::with_capacity(self.depth);
+        let mut pos = Vec::with_capacity(self.depth);
+
+        let mut index = index;
+        for level in 0..self.depth {
+            // siblings right of the last inserted leaf are empty subtrees
+            let sibling = self.store.get(level, index ^ 1).unwrap_or(self.zeroes[level]);
+            siblings.push(sibling);
+            pos.push(index & 1 == 1);
+            index >>= 1;
         }
 
-        (siblings, pos)
+        Ok((siblings, pos))
+    }
+
+    /// Returns the [`MerkleProof`] of `leaf`, the first one if it was inserted several times.
+    pub fn proof(&self, leaf: H::Node) -> Result<MerkleProof<H::Node>, Error> {
+        let index = self.first_index(&leaf).ok_or(Error::UnknownLeaf)?;
+        self.proof_at(index)
+    }
+
+    /// First position of `leaf`, if it is in the tree.
+    fn first_index(&self, leaf: &H::Node) -> Option<usize> {
+        self.indices.get(&leaf.to_bytes()).and_then(|positions| positions.iter().next().copied())
+    }
+
+    /// Returns the [`MerkleProof`] of the leaf at `index`.
+    pub fn proof_at(&self, index: usize) -> Result<MerkleProof<H::Node>, Error> {
+        let (siblings, _) = self.witness_at(index)?;
+        MerkleProof::new(index as u64, siblings)
+    }
+
+    /// Returns the [`Insertion`] event of the leaf at `index` for members following the tree.
+    ///
+    /// The frontier and the root are read from the current nodes, so the event is meant
+    /// to be published right after the leaf is appended.
+    pub fn insertion_at(&self, index: usize) -> Result<Insertion<H::Node>, Error> {
+        let (siblings, pos) = self.witness_at(index)?;
+        let leaf = self.store.get(0, index).ok_or(Error::CorruptStore("missing leaf"))?;
+        let frontier = siblings
+            .into_iter()
+            .zip(pos)
+            .filter_map(|(sibling, right)| if right { Some(sibling) } else { None })
+            .collect();
+
+        Ok(Insertion { index, leaf, frontier, root: self.root })
     }
 
-    pub fn check_proof(&self, leaf: Fp, siblings: Vec<Fp>, pos: Vec<bool>) -> bool {
+    pub fn check_proof(&self, leaf: H::Node, siblings: Vec<H::Node>, pos: Vec<bool>) -> bool {
         let mut node = leaf;
         for (sibling, p) in siblings.iter().zip(pos.iter()) { 
             if *p {
-                node = IncrementalTree::hash_layer(node, *sibling);
+                node = H::hash_layer(*sibling, node);
             } else {
-                node = IncrementalTree::hash_layer(*sibling, node);
+                node = H::hash_layer(node, *sibling);
             }
         }
 
         node == self.root
     }
 
-    pub fn root(&self) -> Fp {
+    pub fn root(&self) -> H::Node {
         self.root
     }
 
@@ -144,8 +518,41 @@ impl IncrementalTree {
         self.depth
     }
 
-    fn hash_layer(left: Fp, right: Fp) -> Fp {
-        poseidon::Hash::init(P128Pow5T3, ConstantLength::<2>).hash([left, right])
+    /// Recent roots of the tree, the current one last.
+    pub fn root_history(&self) -> &RootHistory<H::Node> {
+        &self.history
+    }
+
+    /// Changes how many recent roots are kept, dropping the oldest ones if needed.
+    pub fn set_root_history_size(&mut self, size: usize) {
+        let mut history = RootHistory::new(size);
+        for (root, leaves) in self.history.iter() {
+            history.push(*root, *leaves);
+        }
+        self.history = history;
+    }
+
+    /// Whether `root` is the current root or one of the recent ones.
+    pub fn is_acceptable_root(&self, root: &H::Node) -> bool {
+        self.history.get(root).is_some()
+    }
+
+    /// Number of leaves the tree can hold, `2^depth`.
+    pub fn capacity(&self) -> u64 {
+        1u64 << self.depth
+    }
+
+    /// Number of leaves inserted so far.
+    pub fn len(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.position as u64 >= self.capacity()
     }
 }
 
@@ -153,8 +560,28 @@ impl IncrementalTree {
 #[cfg(test)]
 mod test {
 
-    use super::{IncrementalTree};
+    use super::{IncrementalTree, RootHistory, RootPolicy, MAX_DEPTH};
+    use super::hasher::{PoseidonHasher, Sha256Hasher, TreeHasher, VestaPoseidonHasher};
+    use super::store::MemoryStore;
     use crate::halo2::pasta::Fp;
+    use pasta_curves::vesta;
+    use crate::error::Error;
+    use ff::Field;
+
+    /// Root of a tree of `depth` with `leaves` first and the zero value everywhere else.
+    fn reference_root<H: TreeHasher>(zero_value: H::Node, depth: usize, leaves: &[H::Node]) -> H::Node {
+        let mut layer: Vec<H::Node> = leaves.to_vec();
+        layer.resize(1 << depth, zero_value);
+
+        for _ in 0..depth {
+            layer = layer
+                .chunks(2)
+                .map(|pair| H::hash_layer(pair[0], pair[1]))
+                .collect();
+        }
+
+        layer[0]
+    }
     #[test]
     fn construct() {
         let mut tree = IncrementalTree::new(Fp::one(), 20);
@@ -167,7 +594,261 @@ mod test {
         tree.insert(Fp::from(7));
 
         let leaf = Fp::from(7);
-        let (siblings, pos) = tree.witness(leaf);
-        println!("{:?}", tree.check_proof(leaf, siblings, pos));
+        let (siblings, pos) = tree.witness(leaf).unwrap();
+        assert!(tree.check_proof(leaf, siblings, pos));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(IncrementalTree::try_new(Fp::zero(), MAX_DEPTH + 1), Err(Error::DepthTooLarge(33))));
+
+        let mut tree = IncrementalTree::try_new(Fp::zero(), 2).unwrap();
+        assert!(matches!(tree.try_insert(Fp::zero()), Err(Error::ZeroLeaf)));
+
+        for leaf in 1..=4 {
+            tree.try_insert(Fp::from(leaf)).unwrap();
+        }
+        let root = tree.root();
+        assert!(matches!(tree.try_insert(Fp::from(5)), Err(Error::TreeFull)));
+        assert_eq!(root, tree.root());
+
+        assert!(matches!(tree.witness(Fp::from(5)), Err(Error::UnknownLeaf)));
+    }
+
+    #[test]
+    fn capacity() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..4 {
+            let zero_value = Fp::random(&mut rng);
+            let mut tree = IncrementalTree::new(zero_value, 4);
+            assert_eq!(tree.capacity(), 16);
+            assert!(tree.is_empty());
+            assert_eq!(tree.root(), reference_root::<PoseidonHasher>(zero_value, 4, &[]));
+
+            let mut leaves = vec![];
+            while !tree.is_full() {
+                let leaf = Fp::random(&mut rng);
+                tree.try_insert(leaf).unwrap();
+                leaves.push(leaf);
+
+                assert_eq!(tree.len(), leaves.len());
+                assert_eq!(tree.root(), reference_root::<PoseidonHasher>(zero_value, 4, &leaves));
+            }
+
+            assert_eq!(tree.len(), 16);
+            assert!(matches!(tree.try_insert(Fp::random(&mut rng)), Err(Error::TreeFull)));
+            assert_eq!(tree.len(), 16);
+        }
+    }
+
+    #[test]
+    fn witness_at() {
+        let mut rng = rand::thread_rng();
+        let mut tree = IncrementalTree::new(Fp::zero(), 5);
+
+        let leaves: Vec<Fp> = (0..21).map(|_| Fp::random(&mut rng)).collect();
+        for leaf in leaves.iter() {
+            tree.insert(*leaf);
+        }
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let (siblings, pos) = tree.witness_at(index).unwrap();
+            assert!(tree.check_proof(*leaf, siblings.clone(), pos.clone()));
+            assert_eq!(tree.witness(*leaf).unwrap(), (siblings, pos));
+        }
+
+        assert!(matches!(tree.witness_at(leaves.len()), Err(Error::IndexOutOfBounds(21))));
+
+        // a repeated commitment keeps the first position
+        tree.insert(leaves[3]);
+        assert_eq!(tree.witness(leaves[3]).unwrap(), tree.witness_at(3).unwrap());
+        let (siblings, pos) = tree.witness_at(leaves.len()).unwrap();
+        assert!(tree.check_proof(leaves[3], siblings, pos));
+
+        // removing the first copy falls back to the next one
+        tree.delete(3).unwrap();
+        assert_eq!(tree.witness(leaves[3]).unwrap(), tree.witness_at(leaves.len()).unwrap());
+        tree.update(leaves.len(), Fp::random(&mut rng)).unwrap();
+        assert!(matches!(tree.witness(leaves[3]), Err(Error::UnknownLeaf)));
+    }
+
+    #[test]
+    fn update_and_delete() {
+        let mut rng = rand::thread_rng();
+        let zero_value = Fp::zero();
+        let mut tree = IncrementalTree::new(zero_value, 4);
+
+        let mut leaves: Vec<Fp> = (0..11).map(|_| Fp::random(&mut rng)).collect();
+        for leaf in leaves.iter() {
+            tree.insert(*leaf);
+        }
+
+        let check = |tree: &IncrementalTree, leaves: &[Fp]| {
+            assert_eq!(tree.root(), reference_root::<PoseidonHasher>(zero_value, 4, leaves));
+            for (index, leaf) in leaves.iter().enumerate() {
+                let (siblings, pos) = tree.witness_at(index).unwrap();
+                assert!(tree.check_proof(*leaf, siblings, pos));
+            }
+        };
+
+        leaves[4] = Fp::random(&mut rng);
+        tree.update(4, leaves[4]).unwrap();
+        check(&tree, &leaves);
+        assert_eq!(tree.witness(leaves[4]).unwrap(), tree.witness_at(4).unwrap());
+
+        let removed = leaves[7];
+        leaves[7] = zero_value;
+        tree.delete(7).unwrap();
+        check(&tree, &leaves);
+        assert!(matches!(tree.witness(removed), Err(Error::UnknownLeaf)));
+
+        // the last leaf can be removed too, appends continue after it
+        leaves[10] = zero_value;
+        tree.delete(10).unwrap();
+        check(&tree, &leaves);
+
+        leaves.push(Fp::random(&mut rng));
+        tree.insert(leaves[11]);
+        check(&tree, &leaves);
+
+        assert!(matches!(tree.update(3, zero_value), Err(Error::ZeroLeaf)));
+        assert!(matches!(tree.update(12, Fp::one()), Err(Error::IndexOutOfBounds(12))));
+        assert!(matches!(tree.delete(12), Err(Error::IndexOutOfBounds(12))));
+    }
+
+    #[test]
+    fn root_history() {
+        let mut history = RootHistory::new(2);
+        history.push(Fp::from(1), 1);
+        history.push(Fp::from(2), 2);
+        history.push(Fp::from(3), 3);
+        assert!(!history.is_acceptable_root(&Fp::from(1)));
+        assert_eq!(history.get(&Fp::from(2)), Some(2));
+        assert_eq!(history.len(), 2);
+        history.revoke(2);
+        assert_eq!(history.get(&Fp::from(2)), Some(2));
+        assert!(!history.is_acceptable_root(&Fp::from(3)));
+
+        let mut tree = IncrementalTree::new(Fp::zero(), 4);
+        tree.set_root_history_size(3);
+
+        let empty_root = tree.root();
+        tree.insert(Fp::from(1));
+        let first_root = tree.root();
+        let proof = tree.proof_at(0).unwrap();
+        tree.insert(Fp::from(2));
+        let second_root = tree.root();
+        assert!(tree.is_acceptable_root(&empty_root));
+        assert!(tree.is_acceptable_root(&first_root));
+
+        // roots holding the removed leaf are no longer accepted, older ones still are
+        tree.delete(1).unwrap();
+        assert!(tree.is_acceptable_root(&empty_root));
+        assert!(!tree.is_acceptable_root(&second_root));
+        assert!(tree.is_acceptable_root(&tree.root()));
+        // deleting the last leaf brings back the root with one leaf
+        assert_eq!(tree.root(), first_root);
+        assert_eq!(tree.root_history().get(&first_root), Some(2));
+        assert!(!tree.is_acceptable_root(&Fp::from(5)));
+
+        // the remaining member's proof against a root from before the removal stays accepted
+        tree.insert(Fp::from(3));
+        tree.insert(Fp::from(4));
+        let third_root = tree.root();
+        assert!(proof.verify(first_root, Fp::from(1)));
+        tree.update(3, Fp::from(6)).unwrap();
+        assert!(tree.is_acceptable_root(&first_root));
+        assert!(tree.is_acceptable_root(&tree.root()));
+        assert!(!tree.is_acceptable_root(&third_root));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut rng = rand::thread_rng();
+        let leaves: Vec<Fp> = (0..19).map(|_| Fp::random(&mut rng)).collect();
+
+        let mut tree = IncrementalTree::new(Fp::zero(), 5);
+        tree.insert_batch(&leaves).unwrap();
+        tree.insert(leaves[2]);
+        let root = tree.root();
+        tree.truncate(20).unwrap();
+        assert_eq!(tree.root(), root);
+
+        // the copy of leaves[2] goes first
+        for len in [13, 8, 5, 0] {
+            tree.truncate(len).unwrap();
+            let mut reference = IncrementalTree::new(Fp::zero(), 5);
+            reference.insert_batch(&leaves[..len]).unwrap();
+
+            assert_eq!(tree.len(), len);
+            assert_eq!(tree.root(), reference.root());
+            assert_eq!(tree.root_history().len(), 1);
+            for (index, leaf) in leaves[..len].iter().enumerate() {
+                assert_eq!(tree.witness(*leaf).unwrap(), reference.witness_at(index).unwrap());
+            }
+            assert!(matches!(tree.witness(leaves[len]), Err(Error::UnknownLeaf)));
+        }
+
+        // appends continue from the truncated length
+        tree.insert_batch(&leaves[..11]).unwrap();
+        tree.truncate(9).unwrap();
+        tree.insert(leaves[9]);
+        assert_eq!(tree.root(), reference_root::<PoseidonHasher>(Fp::zero(), 5, &leaves[..10]));
+        assert!(matches!(tree.truncate(11), Err(Error::IndexOutOfBounds(11))));
+    }
+
+    #[test]
+    fn insert_batch() {
+        let mut rng = rand::thread_rng();
+        let leaves: Vec<Fp> = (0..37).map(|_| Fp::random(&mut rng)).collect();
+
+        let mut batched = IncrementalTree::new(Fp::zero(), 6);
+        let mut reference = IncrementalTree::new(Fp::zero(), 6);
+
+        // uneven batches starting at odd and even positions
+        for batch in [&leaves[..1], &leaves[1..8], &leaves[8..8], &leaves[8..21], &leaves[21..]] {
+            batched.insert_batch(batch).unwrap();
+            for leaf in batch {
+                reference.insert(*leaf);
+            }
+
+            assert_eq!(batched.root(), reference.root());
+            assert_eq!(batched.len(), reference.len());
+        }
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            assert_eq!(batched.witness(*leaf).unwrap(), reference.witness_at(index).unwrap());
+        }
+
+        let root = batched.root();
+        assert!(matches!(batched.insert_batch(&[Fp::one(), Fp::zero()]), Err(Error::ZeroLeaf)));
+        let too_many = vec![Fp::one(); 28];
+        assert!(matches!(batched.insert_batch(&too_many), Err(Error::TreeFull)));
+        assert_eq!(batched.root(), root);
+
+        batched.insert_batch(&too_many[1..]).unwrap();
+        assert!(batched.is_full());
+    }
+
+    #[test]
+    fn hashers() {
+        let mut rng = rand::thread_rng();
+
+        let leaves: Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+        let mut tree = IncrementalTree::<Sha256Hasher, _>::open(MemoryStore::new(), [0u8; 32], 3).unwrap();
+        tree.insert_batch(&leaves).unwrap();
+        assert_eq!(tree.root(), reference_root::<Sha256Hasher>([0u8; 32], 3, &leaves));
+        let (siblings, pos) = tree.witness(leaves[2]).unwrap();
+        assert!(tree.check_proof(leaves[2], siblings, pos));
+
+        let leaves: Vec<vesta::Base> = (0..5).map(|_| vesta::Base::random(&mut rng)).collect();
+        let mut tree = IncrementalTree::<VestaPoseidonHasher, _>::open(MemoryStore::new(), vesta::Base::zero(), 3).unwrap();
+        for leaf in leaves.iter() {
+            tree.insert(*leaf);
+        }
+        assert_eq!(tree.root(), reference_root::<VestaPoseidonHasher>(vesta::Base::zero(), 3, &leaves));
+        let (siblings, pos) = tree.witness_at(4).unwrap();
+        assert!(tree.check_proof(leaves[4], siblings, pos));
     }
 }
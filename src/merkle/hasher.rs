@@ -0,0 +1,95 @@
+/*
+Hash functions the native Merkle trees can be built with.
+
+Only `PoseidonHasher` matches the `InclusionProof` gadget, the others are for trees
+that are never opened in a circuit.
+*/
+use crate::poseidon::{self, P128Pow5T3, ConstantLength};
+use ff::PrimeField;
+use pasta_curves::{pallas, vesta};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// A tree node with a fixed 32-byte encoding, used for indexing and storage.
+pub trait TreeNode: Copy + PartialEq + fmt::Debug {
+    fn to_bytes(&self) -> [u8; 32];
+
+    /// Decodes a node, returning `None` for a non-canonical encoding.
+    fn from_bytes(bytes: &[u8; 32]) -> Option<Self>;
+}
+
+impl TreeNode for pallas::Base {
+    fn to_bytes(&self) -> [u8; 32] {
+        self.to_repr()
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Option::from(pallas::Base::from_repr(*bytes))
+    }
+}
+
+impl TreeNode for vesta::Base {
+    fn to_bytes(&self) -> [u8; 32] {
+        self.to_repr()
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Option::from(vesta::Base::from_repr(*bytes))
+    }
+}
+
+impl TreeNode for [u8; 32] {
+    fn to_bytes(&self) -> [u8; 32] {
+        *self
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Some(*bytes)
+    }
+}
+
+pub trait TreeHasher {
+    type Node: TreeNode;
+
+    /// Hashes two children into their parent.
+    fn hash_layer(left: Self::Node, right: Self::Node) -> Self::Node;
+}
+
+/// Poseidon over `pallas::Base`, the hash used by the RLN circuit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PoseidonHasher;
+
+impl TreeHasher for PoseidonHasher {
+    type Node = pallas::Base;
+
+    fn hash_layer(left: pallas::Base, right: pallas::Base) -> pallas::Base {
+        poseidon::Hash::init(P128Pow5T3, ConstantLength::<2>).hash([left, right])
+    }
+}
+
+/// Poseidon over `vesta::Base`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VestaPoseidonHasher;
+
+impl TreeHasher for VestaPoseidonHasher {
+    type Node = vesta::Base;
+
+    fn hash_layer(left: vesta::Base, right: vesta::Base) -> vesta::Base {
+        poseidon::Hash::init(P128Pow5T3, ConstantLength::<2>).hash([left, right])
+    }
+}
+
+/// SHA-256 of the concatenated children.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha256Hasher;
+
+impl TreeHasher for Sha256Hasher {
+    type Node = [u8; 32];
+
+    fn hash_layer(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+}
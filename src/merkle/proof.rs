@@ -0,0 +1,283 @@
+/*
+Merkle path of a leaf, as sent by whoever keeps the tree to the member proving
+membership.
+
+The byte encoding is the depth as u32 and the leaf index as u64, both little-endian,
+followed by the siblings from the leaf level up in their 32-byte encoding. The path
+bits are the bits of the leaf index, so they are not encoded. The JSON encoding, behind
+the `serde` feature, holds the same fields with the siblings as hex strings of their
+32-byte encoding.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use super::hasher::{PoseidonHasher, TreeHasher, TreeNode};
+use super::MAX_DEPTH;
+#[cfg(feature = "serde")]
+use crate::utils::{from_hex, to_hex};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::io;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleProof<N = Fp> {
+    pub leaf_index: u64,
+    /// Siblings from the leaf level up.
+    pub siblings: Vec<N>,
+    /// Whether the node on each level is a right child, the bits of `leaf_index`.
+    pub path: Vec<bool>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct MerkleProofJson {
+    leaf_index: u64,
+    siblings: Vec<String>,
+    path: Vec<bool>,
+}
+
+/// Path bits of the leaf at `leaf_index` in a tree of `depth`.
+fn path_bits(leaf_index: u64, depth: usize) -> Vec<bool> {
+    (0..depth).map(|level| (leaf_index >> level) & 1 == 1).collect()
+}
+
+impl<N: TreeNode> MerkleProof<N> {
+    /// Builds the proof of the leaf at `leaf_index`, deriving the path bits from it.
+    pub fn new(leaf_index: u64, siblings: Vec<N>) -> Result<Self, Error> {
+        let depth = siblings.len();
+        if depth > MAX_DEPTH {
+            return Err(Error::DepthTooLarge(depth));
+        }
+        if leaf_index >> depth != 0 {
+            return Err(Error::InvalidMerkleProof("leaf index does not fit the depth"));
+        }
+
+        Ok(MerkleProof {
+            leaf_index,
+            siblings,
+            path: path_bits(leaf_index, depth),
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Whether the path bits are the bits of the leaf index.
+    fn is_consistent(&self) -> bool {
+        let depth = self.depth();
+        depth <= MAX_DEPTH && self.leaf_index >> depth == 0 && self.path == path_bits(self.leaf_index, depth)
+    }
+
+    /// Root of the tree holding `leaf` at the end of this path.
+    pub fn root_with<H: TreeHasher<Node = N>>(&self, leaf: N) -> N {
+        self.siblings.iter().zip(self.path.iter()).fold(leaf, |node, (sibling, right)| {
+            if *right {
+                H::hash_layer(*sibling, node)
+            } else {
+                H::hash_layer(node, *sibling)
+            }
+        })
+    }
+
+    /// Whether `leaf` is in the tree with `root` hashed with `H`.
+    pub fn verify_with<H: TreeHasher<Node = N>>(&self, root: N, leaf: N) -> bool {
+        self.is_consistent() && self.root_with::<H>(leaf) == root
+    }
+
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&(self.depth() as u32).to_le_bytes())?;
+        writer.write_all(&self.leaf_index.to_le_bytes())?;
+        for sibling in self.siblings.iter() {
+            writer.write_all(&sibling.to_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut depth = [0u8; 4];
+        reader.read_exact(&mut depth)?;
+        let depth = u32::from_le_bytes(depth) as usize;
+        if depth > MAX_DEPTH {
+            return Err(Error::DepthTooLarge(depth));
+        }
+
+        let mut leaf_index = [0u8; 8];
+        reader.read_exact(&mut leaf_index)?;
+        let leaf_index = u64::from_le_bytes(leaf_index);
+
+        let mut siblings = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            let mut bytes = [0u8; 32];
+            reader.read_exact(&mut bytes)?;
+            siblings.push(N::from_bytes(&bytes).ok_or(Error::InvalidMerkleProof("non-canonical sibling"))?);
+        }
+
+        Self::new(leaf_index, siblings)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + 32 * self.depth());
+        self.write(&mut bytes).expect("writing to a vec does not fail");
+        bytes
+    }
+
+    /// Decodes a proof, rejecting trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = bytes;
+        let proof = Self::read(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(Error::InvalidMerkleProof("trailing bytes"));
+        }
+        Ok(proof)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        let json = MerkleProofJson {
+            leaf_index: self.leaf_index,
+            siblings: self.siblings.iter().map(|sibling| to_hex(&sibling.to_bytes())).collect(),
+            path: self.path.clone(),
+        };
+        serde_json::to_string(&json).expect("proof serializes to json")
+    }
+
+    /// Decodes a proof, rejecting path bits that are not the bits of the leaf index.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let json: MerkleProofJson =
+            serde_json::from_str(json).map_err(|_| Error::InvalidMerkleProof("invalid json"))?;
+
+        let siblings = json
+            .siblings
+            .iter()
+            .map(|hex| {
+                from_hex(hex)
+                    .and_then(|bytes| N::from_bytes(&bytes))
+                    .ok_or(Error::InvalidMerkleProof("invalid sibling"))
+            })
+            .collect::<Result<Vec<N>, Error>>()?;
+
+        let proof = Self::new(json.leaf_index, siblings)?;
+        if proof.path != json.path {
+            return Err(Error::InvalidMerkleProof("path does not match the leaf index"));
+        }
+        Ok(proof)
+    }
+}
+
+impl MerkleProof {
+    /// Whether `leaf` is in the Poseidon tree with `root`.
+    pub fn verify(&self, root: Fp, leaf: Fp) -> bool {
+        self.verify_with::<PoseidonHasher>(root, leaf)
+    }
+
+    /// Siblings and path bits as witnesses of a circuit of depth `DEPTH`.
+    pub fn circuit_witness<const DEPTH: usize>(&self) -> Result<([Option<Fp>; DEPTH], [Option<bool>; DEPTH]), Error> {
+        if self.depth() != DEPTH {
+            return Err(Error::InvalidMerkleProof("depth does not match the circuit"));
+        }
+
+        let siblings: Vec<Option<Fp>> = self.siblings.iter().map(|sibling| Some(*sibling)).collect();
+        let path: Vec<Option<bool>> = self.path.iter().map(|bit| Some(*bit)).collect();
+
+        Ok((siblings.try_into().unwrap(), path.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MerkleProof;
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use crate::merkle::IncrementalTree;
+    use crate::merkle::hasher::Sha256Hasher;
+    use crate::merkle::store::MemoryStore;
+
+    #[test]
+    fn verify_and_encode() {
+        let mut tree = IncrementalTree::new(Fp::zero(), 8);
+        let leaves: Vec<Fp> = (1..=11u64).map(Fp::from).collect();
+        for leaf in leaves.iter() {
+            tree.insert(*leaf);
+        }
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(*leaf).unwrap();
+            assert_eq!(proof.leaf_index, index as u64);
+            assert_eq!(proof, tree.proof_at(index).unwrap());
+            assert!(proof.verify(tree.root(), *leaf));
+            assert!(!proof.verify(tree.root(), *leaf + Fp::one()));
+            assert!(!proof.verify(tree.root() + Fp::one(), *leaf));
+
+            assert_eq!(MerkleProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+        }
+
+        let proof = tree.proof(leaves[5]).unwrap();
+        let (siblings, path) = proof.circuit_witness::<8>().unwrap();
+        assert_eq!(siblings[0], Some(proof.siblings[0]));
+        assert_eq!(path, [Some(true), Some(false), Some(true), Some(false), Some(false), Some(false), Some(false), Some(false)]);
+        assert!(matches!(proof.circuit_witness::<16>(), Err(Error::InvalidMerkleProof(_))));
+
+        // path bits that disagree with the index
+        let mut forged = proof.clone();
+        forged.path[0] = false;
+        assert!(!forged.verify(tree.root(), leaves[5]));
+
+        // other hashers
+        let mut tree = IncrementalTree::<Sha256Hasher, _>::open(MemoryStore::new(), [0u8; 32], 4).unwrap();
+        tree.insert([1u8; 32]);
+        tree.insert([2u8; 32]);
+        let proof = tree.proof([2u8; 32]).unwrap();
+        assert!(proof.verify_with::<Sha256Hasher>(tree.root(), [2u8; 32]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_encoding() {
+        let mut tree = IncrementalTree::new(Fp::zero(), 8);
+        for leaf in 1..=11u64 {
+            tree.insert(Fp::from(leaf));
+        }
+        for index in 0..11 {
+            let proof = tree.proof_at(index).unwrap();
+            assert_eq!(MerkleProof::from_json(&proof.to_json()).unwrap(), proof);
+        }
+
+        let proof = tree.proof_at(5).unwrap();
+        let json = proof.to_json().replace("\"path\":[true,", "\"path\":[false,");
+        assert!(matches!(MerkleProof::<Fp>::from_json(&json), Err(Error::InvalidMerkleProof(_))));
+        assert!(matches!(MerkleProof::<Fp>::from_json("{}"), Err(Error::InvalidMerkleProof(_))));
+
+        let mut tree = IncrementalTree::<Sha256Hasher, _>::open(MemoryStore::new(), [0u8; 32], 4).unwrap();
+        tree.insert([1u8; 32]);
+        tree.insert([2u8; 32]);
+        let proof = tree.proof([2u8; 32]).unwrap();
+        assert_eq!(MerkleProof::from_json(&proof.to_json()).unwrap(), proof);
+    }
+
+    #[test]
+    fn reject_malformed() {
+        let mut tree = IncrementalTree::new(Fp::zero(), 4);
+        tree.insert(Fp::from(1));
+        tree.insert(Fp::from(2));
+        let proof = tree.proof(Fp::from(2)).unwrap();
+        let bytes = proof.to_bytes();
+
+        assert!(matches!(MerkleProof::<Fp>::from_bytes(&bytes[..bytes.len() - 1]), Err(Error::Io(_))));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(MerkleProof::<Fp>::from_bytes(&trailing), Err(Error::InvalidMerkleProof(_))));
+
+        // leaf index 16 does not fit in depth 4
+        let mut index = bytes.clone();
+        index[4] = 16;
+        assert!(matches!(MerkleProof::<Fp>::from_bytes(&index), Err(Error::InvalidMerkleProof(_))));
+
+        // sibling above the field modulus
+        let mut sibling = bytes.clone();
+        sibling[12..44].copy_from_slice(&[0xff; 32]);
+        assert!(matches!(MerkleProof::<Fp>::from_bytes(&sibling), Err(Error::InvalidMerkleProof(_))));
+    }
+}
@@ -0,0 +1,264 @@
+/*
+Append-only Merkle tree with four children per node.
+
+A node is the width-5 Poseidon hash of its four children, so the tree needs half the
+levels of a binary one for the same number of leaves. The path of a leaf holds, on
+each level, the three other children in order and the position of the node among
+the four, which is what the `QuaternaryInclusionProof` gadget takes.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use crate::poseidon::{self, ConstantLength, P128Pow5T5};
+use super::hasher::TreeNode;
+use super::store::{MemoryStore, NodeStore};
+use super::{RootHistory, RootPolicy, DEFAULT_ROOT_HISTORY_SIZE};
+use std::collections::HashMap;
+
+/// Largest supported depth, which holds as many leaves as a binary tree of [`super::MAX_DEPTH`].
+pub const QUATERNARY_MAX_DEPTH: usize = 16;
+
+/// Hashes four children into their parent.
+pub fn hash_children(children: [Fp; 4]) -> Fp {
+    poseidon::Hash::init(P128Pow5T5, ConstantLength::<4>).hash(children)
+}
+
+/// Parent of `node` placed at `pos` among `siblings`.
+fn parent(node: Fp, siblings: &[Fp; 3], pos: usize) -> Fp {
+    let mut children = [Fp::zero(); 4];
+    let mut siblings = siblings.iter();
+    for (i, child) in children.iter_mut().enumerate() {
+        *child = if i == pos { node } else { *siblings.next().unwrap() };
+    }
+    hash_children(children)
+}
+
+pub struct QuaternaryTree {
+    root: Fp,
+    zeroes: Vec<Fp>,
+    store: MemoryStore<Fp>,
+    depth: usize,
+    position: usize,
+    // encoding of each leaf mapped to its first position
+    indices: HashMap<[u8; 32], usize>,
+    history: RootHistory<Fp>,
+}
+
+impl RootPolicy for QuaternaryTree {
+    fn is_acceptable_root(&self, root: &Fp) -> bool {
+        self.history.get(root).is_some()
+    }
+}
+
+impl QuaternaryTree {
+    /// Creates an empty tree, panicking if `depth` is above [`QUATERNARY_MAX_DEPTH`].
+    pub fn new(zero_value: Fp, depth: usize) -> Self {
+        Self::try_new(zero_value, depth).expect("MAX DEPTH EXCEEDED")
+    }
+
+    /// Creates an empty tree whose empty slots hold `zero_value`.
+    pub fn try_new(zero_value: Fp, depth: usize) -> Result<Self, Error> {
+        if depth > QUATERNARY_MAX_DEPTH {
+            return Err(Error::DepthTooLarge(depth));
+        }
+
+        let mut zeroes = vec![zero_value];
+        for level in 0..depth {
+            zeroes.push(hash_children([zeroes[level]; 4]));
+        }
+
+        let mut history = RootHistory::new(DEFAULT_ROOT_HISTORY_SIZE);
+        history.push(zeroes[depth], 0);
+
+        Ok(QuaternaryTree {
+            root: zeroes[depth],
+            zeroes,
+            store: MemoryStore::new(),
+            depth,
+            position: 0,
+            indices: HashMap::new(),
+            history,
+        })
+    }
+
+    /// Appends `leaf`, panicking on the zero value or a full tree.
+    pub fn insert(&mut self, leaf: Fp) {
+        if let Err(err) = self.try_insert(leaf) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Appends `leaf` at the next free position.
+    pub fn try_insert(&mut self, leaf: Fp) -> Result<(), Error> {
+        if leaf == self.zeroes[0] {
+            return Err(Error::ZeroLeaf);
+        }
+
+        if self.is_full() {
+            return Err(Error::TreeFull);
+        }
+
+        self.store.set(0, self.position, leaf)?;
+
+        let (siblings, pos) = self.witness_at(self.position)?;
+        let mut node = leaf;
+        let mut index = self.position;
+        for (level, (siblings, pos)) in siblings.iter().zip(pos).enumerate() {
+            if level > 0 {
+                self.store.set(level, index, node)?;
+            }
+            node = parent(node, siblings, pos as usize);
+            index /= 4;
+        }
+
+        self.indices.entry(leaf.to_bytes()).or_insert(self.position);
+        self.position += 1;
+        self.root = node;
+        self.history.push(self.root, self.position);
+        Ok(())
+    }
+
+    /// Returns the siblings and positions of `leaf`.
+    ///
+    /// If the same leaf was inserted several times, the path of the first one is returned.
+    pub fn witness(&self, leaf: Fp) -> Result<(Vec<[Fp; 3]>, Vec<u8>), Error> {
+        let index = *self.indices.get(&leaf.to_bytes()).ok_or(Error::UnknownLeaf)?;
+        self.witness_at(index)
+    }
+
+    /// Returns the siblings and positions of the leaf at `index`.
+    ///
+    /// On each level the position is the index of the node among its siblings, from 0
+    /// to 3, and the siblings are the three other children from left to right.
+    pub fn witness_at(&self, index: usize) -> Result<(Vec<[Fp; 3]>, Vec<u8>), Error> {
+        // the slot being inserted is already in the store
+        if index >= self.store.len(0) {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+
+        let mut siblings = Vec::with_capacity(self.depth);
+        let mut pos = Vec::with_capacity(self.depth);
+
+        let mut index = index;
+        for level in 0..self.depth {
+            let first = index - index % 4;
+            let mut level_siblings = [Fp::zero(); 3];
+            let others = (first..first + 4).filter(|i| *i != index);
+            for (sibling, i) in level_siblings.iter_mut().zip(others) {
+                // siblings right of the last inserted leaf are empty subtrees
+                *sibling = self.store.get(level, i).unwrap_or(self.zeroes[level]);
+            }
+
+            siblings.push(level_siblings);
+            pos.push((index % 4) as u8);
+            index /= 4;
+        }
+
+        Ok((siblings, pos))
+    }
+
+    pub fn check_proof(&self, leaf: Fp, siblings: &[[Fp; 3]], pos: &[u8]) -> bool {
+        if siblings.len() != self.depth || pos.iter().any(|pos| *pos > 3) {
+            return false;
+        }
+
+        let node = siblings
+            .iter()
+            .zip(pos.iter())
+            .fold(leaf, |node, (siblings, pos)| parent(node, siblings, *pos as usize));
+
+        node == self.root
+    }
+
+    pub fn root(&self) -> Fp {
+        self.root
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Recent roots of the tree, the current one last.
+    pub fn root_history(&self) -> &RootHistory<Fp> {
+        &self.history
+    }
+
+    /// Number of leaves the tree can hold, `4^depth`.
+    pub fn capacity(&self) -> u64 {
+        1u64 << (2 * self.depth)
+    }
+
+    /// Number of leaves inserted so far.
+    pub fn len(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.position as u64 >= self.capacity()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash_children, QuaternaryTree, QUATERNARY_MAX_DEPTH};
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use crate::merkle::RootPolicy;
+
+    #[test]
+    fn insert_and_witness() {
+        let depth = 3;
+        let mut tree = QuaternaryTree::new(Fp::zero(), depth);
+        let leaves: Vec<Fp> = (1..=21u64).map(Fp::from).collect();
+
+        let mut roots = vec![tree.root()];
+        for leaf in leaves.iter() {
+            tree.insert(*leaf);
+            roots.push(tree.root());
+        }
+
+        // reference root hashing every level in full
+        let mut level: Vec<Fp> = leaves.clone();
+        level.resize(64, Fp::zero());
+        for _ in 0..depth {
+            level = level
+                .chunks(4)
+                .map(|children| hash_children([children[0], children[1], children[2], children[3]]))
+                .collect();
+        }
+        assert_eq!(level, vec![tree.root()]);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let (siblings, pos) = tree.witness(*leaf).unwrap();
+            assert_eq!(pos[0] as usize, index % 4);
+            assert!(tree.check_proof(*leaf, &siblings, &pos));
+            assert!(!tree.check_proof(*leaf + Fp::one(), &siblings, &pos));
+        }
+
+        assert!(roots.iter().all(|root| tree.is_acceptable_root(root)));
+        assert!(!tree.is_acceptable_root(&Fp::one()));
+    }
+
+    #[test]
+    fn errors() {
+        let mut tree = QuaternaryTree::new(Fp::zero(), 1);
+        assert_eq!(tree.capacity(), 4);
+
+        assert!(matches!(tree.try_insert(Fp::zero()), Err(Error::ZeroLeaf)));
+        for leaf in 1..=4u64 {
+            tree.try_insert(Fp::from(leaf)).unwrap();
+        }
+        assert!(tree.is_full());
+        assert!(matches!(tree.try_insert(Fp::from(5)), Err(Error::TreeFull)));
+
+        assert!(matches!(tree.witness(Fp::from(5)), Err(Error::UnknownLeaf)));
+        assert!(matches!(tree.witness_at(4), Err(Error::IndexOutOfBounds(4))));
+        assert!(matches!(
+            QuaternaryTree::try_new(Fp::zero(), QUATERNARY_MAX_DEPTH + 1),
+            Err(Error::DepthTooLarge(_))
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+/*
+Sparse Merkle tree over field elements.
+
+A key is stored as the leaf at the position given by all of its bits, empty slots
+hold zero. Every key has a slot of its own, so any key can be added, and a key is
+absent when the leaf at its position is not the key itself. Keys must be below
+2^depth; a tree of depth `KEY_BITS` holds any key below 2^254, which is the bound
+that keeps the bits of a key unique in the circuit.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use super::hasher::{PoseidonHasher, TreeHasher};
+use ff::PrimeField;
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+/// Largest number of bits a key is decomposed into, which bounds the tree depth.
+pub const KEY_BITS: usize = 254;
+
+/// Path bits of `key`, least significant first, for a tree of `depth`.
+///
+/// Fails if `key` does not fit in `depth` bits.
+pub fn key_bits(key: Fp, depth: usize) -> Result<Vec<bool>, Error> {
+    let repr = key.to_repr();
+    let bit = |i: usize| (repr[i / 8] >> (i % 8)) & 1 == 1;
+
+    if (depth..256).any(bit) {
+        return Err(Error::KeyOutOfRange);
+    }
+
+    Ok((0..depth).map(bit).collect())
+}
+
+/// Siblings of a slot and the leaf it holds, zero if the slot is empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMerkleProof {
+    pub leaf: Fp,
+    pub siblings: Vec<Fp>,
+}
+
+impl SparseMerkleProof {
+    fn root(&self, key: Fp) -> Option<Fp> {
+        let bits = key_bits(key, self.siblings.len()).ok()?;
+
+        let root = self.siblings.iter().zip(bits).fold(self.leaf, |node, (sibling, bit)| {
+            if bit {
+                PoseidonHasher::hash_layer(*sibling, node)
+            } else {
+                PoseidonHasher::hash_layer(node, *sibling)
+            }
+        });
+        Some(root)
+    }
+
+    /// Whether the proof shows that `key` is in the tree with `root`.
+    pub fn verify_membership(&self, root: Fp, key: Fp) -> bool {
+        self.leaf == key && self.root(key) == Some(root)
+    }
+
+    /// Whether the proof shows that `key` is not in the tree with `root`.
+    pub fn verify_non_membership(&self, root: Fp, key: Fp) -> bool {
+        self.leaf != key && self.root(key) == Some(root)
+    }
+}
+
+pub struct SparseMerkleTree {
+    root: Fp,
+    zeroes: Vec<Fp>,
+    // non-empty nodes by level and position on that level
+    nodes: HashMap<(usize, BigUint), Fp>,
+    depth: usize,
+    len: usize,
+}
+
+impl SparseMerkleTree {
+    /// Creates an empty tree for keys below `2^depth`, `depth` must be at most [`KEY_BITS`].
+    pub fn new(depth: usize) -> Result<Self, Error> {
+        if depth > KEY_BITS {
+            return Err(Error::DepthTooLarge(depth));
+        }
+
+        let mut zeroes = vec![Fp::zero()];
+        for level in 0..depth {
+            zeroes.push(PoseidonHasher::hash_layer(zeroes[level], zeroes[level]));
+        }
+
+        Ok(SparseMerkleTree {
+            root: zeroes[depth],
+            zeroes,
+            nodes: HashMap::new(),
+            depth,
+            len: 0,
+        })
+    }
+
+    fn position(&self, key: Fp) -> Result<BigUint, Error> {
+        let bits = key_bits(key, self.depth)?;
+        Ok(bits.iter().rev().fold(BigUint::from(0u32), |index, bit| (index << 1usize) + (*bit as u32)))
+    }
+
+    fn node(&self, level: usize, index: &BigUint) -> Fp {
+        self.nodes.get(&(level, index.clone())).copied().unwrap_or(self.zeroes[level])
+    }
+
+    /// Writes `leaf` at `index` and rehashes its path.
+    fn set_leaf(&mut self, index: BigUint, leaf: Fp) {
+        let mut node = leaf;
+        let mut index = index;
+        for level in 0..self.depth {
+            if node == self.zeroes[level] {
+                self.nodes.remove(&(level, index.clone()));
+            } else {
+                self.nodes.insert((level, index.clone()), node);
+            }
+
+            let sibling = self.node(level, &(&index ^ BigUint::from(1u32)));
+            node = if index.bit(0) {
+                PoseidonHasher::hash_layer(sibling, node)
+            } else {
+                PoseidonHasher::hash_layer(node, sibling)
+            };
+            index >>= 1usize;
+        }
+
+        self.root = node;
+    }
+
+    /// Adds `key`, doing nothing if it is already in the tree.
+    pub fn insert(&mut self, key: Fp) -> Result<(), Error> {
+        if key == Fp::zero() {
+            return Err(Error::ZeroLeaf);
+        }
+
+        let index = self.position(key)?;
+        if self.node(0, &index) == key {
+            return Ok(());
+        }
+
+        self.set_leaf(index, key);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes `key`, emptying its slot.
+    pub fn remove(&mut self, key: Fp) -> Result<(), Error> {
+        if !self.contains(key) {
+            return Err(Error::UnknownLeaf);
+        }
+
+        let index = self.position(key)?;
+        self.set_leaf(index, Fp::zero());
+        self.len -= 1;
+        Ok(())
+    }
+
+    pub fn contains(&self, key: Fp) -> bool {
+        key != Fp::zero() && matches!(self.position(key), Ok(index) if self.node(0, &index) == key)
+    }
+
+    /// Returns the proof for the slot of `key`, which shows either membership or
+    /// non-membership of the key.
+    pub fn prove(&self, key: Fp) -> Result<SparseMerkleProof, Error> {
+        let mut index = self.position(key)?;
+        let leaf = self.node(0, &index);
+
+        let mut siblings = Vec::with_capacity(self.depth);
+        for level in 0..self.depth {
+            siblings.push(self.node(level, &(&index ^ BigUint::from(1u32))));
+            index >>= 1usize;
+        }
+
+        Ok(SparseMerkleProof { leaf, siblings })
+    }
+
+    pub fn root(&self) -> Fp {
+        self.root
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of keys in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SparseMerkleTree, KEY_BITS};
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use ff::Field;
+
+    #[test]
+    fn membership() {
+        let mut rng = rand::thread_rng();
+        let mut tree = SparseMerkleTree::new(KEY_BITS).unwrap();
+        let empty_root = tree.root();
+
+        let keys: Vec<Fp> = (0..8).map(|_| Fp::random(&mut rng)).collect();
+        for key in keys.iter() {
+            tree.insert(*key).unwrap();
+        }
+        assert_eq!(tree.len(), 8);
+
+        for key in keys.iter() {
+            let proof = tree.prove(*key).unwrap();
+            assert!(proof.verify_membership(tree.root(), *key));
+            assert!(!proof.verify_non_membership(tree.root(), *key));
+        }
+
+        let absent = Fp::random(&mut rng);
+        let proof = tree.prove(absent).unwrap();
+        assert!(proof.verify_non_membership(tree.root(), absent));
+        assert!(!proof.verify_membership(tree.root(), absent));
+        assert!(!proof.verify_non_membership(empty_root, absent));
+
+        // insertion order does not matter and removal restores the previous root
+        let mut reversed = SparseMerkleTree::new(KEY_BITS).unwrap();
+        for key in keys.iter().rev() {
+            reversed.insert(*key).unwrap();
+        }
+        assert_eq!(reversed.root(), tree.root());
+
+        for key in keys.iter() {
+            reversed.remove(*key).unwrap();
+        }
+        assert_eq!(reversed.root(), empty_root);
+        assert!(reversed.is_empty());
+    }
+
+    #[test]
+    fn shared_low_bits_and_errors() {
+        // keys sharing their low 8 bits have slots of their own
+        let mut tree = SparseMerkleTree::new(16).unwrap();
+        let key = Fp::from(3);
+        let other = Fp::from(3 + 256);
+        tree.insert(key).unwrap();
+        tree.insert(key).unwrap();
+        assert_eq!(tree.len(), 1);
+
+        let proof = tree.prove(other).unwrap();
+        assert_eq!(proof.leaf, Fp::zero());
+        assert!(proof.verify_non_membership(tree.root(), other));
+
+        tree.insert(other).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert!(tree.prove(other).unwrap().verify_membership(tree.root(), other));
+        assert!(tree.prove(key).unwrap().verify_membership(tree.root(), key));
+
+        // a key above 2^depth has no slot, neither does one at 2^254
+        let mut tree = SparseMerkleTree::new(8).unwrap();
+        assert!(matches!(tree.insert(other), Err(Error::KeyOutOfRange)));
+        assert!(matches!(tree.prove(other), Err(Error::KeyOutOfRange)));
+        assert!(matches!(tree.insert(Fp::zero()), Err(Error::ZeroLeaf)));
+        assert!(matches!(tree.remove(key), Err(Error::UnknownLeaf)));
+
+        let mut tree = SparseMerkleTree::new(KEY_BITS).unwrap();
+        assert!(matches!(tree.insert(-Fp::one()), Err(Error::KeyOutOfRange)));
+        assert!(matches!(SparseMerkleTree::new(KEY_BITS + 1), Err(Error::DepthTooLarge(_))));
+    }
+}
@@ -0,0 +1,651 @@
+/*
+Storage for the nodes of an `IncrementalTree`.
+
+Level 0 holds the leaves, level `depth - 1` the two children of the root. Nodes on a
+level are written from the left, so a node is either overwritten or appended, and a
+level only shrinks when it is truncated.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use super::hasher::TreeNode;
+use super::MAX_DEPTH;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// State of a tree at the time it was last checkpointed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint<N = Fp> {
+    pub depth: usize,
+    pub leaves: usize,
+    pub root: N,
+}
+
+pub trait NodeStore<N: TreeNode> {
+    /// Node at `index` on `level`, if it was written.
+    fn get(&self, level: usize, index: usize) -> Option<N>;
+
+    /// Writes the node at `index` on `level`, at most one past the last node of the level.
+    fn set(&mut self, level: usize, index: usize, node: N) -> Result<(), Error>;
+
+    /// Drops the nodes of `level` from `len` on.
+    fn truncate(&mut self, level: usize, len: usize) -> Result<(), Error>;
+
+    /// Number of nodes written on `level`.
+    fn len(&self, level: usize) -> usize;
+
+    /// Number of nodes at the start of `level` restored from a snapshot the store wrote
+    /// at a checkpoint and not written since. [`super::IncrementalTree::open`] checks
+    /// only the nodes after them.
+    fn snapshot_len(&self, _level: usize) -> usize {
+        0
+    }
+
+    /// Makes the nodes written so far durable together with `checkpoint`.
+    fn checkpoint(&mut self, checkpoint: &Checkpoint<N>) -> Result<(), Error>;
+
+    fn last_checkpoint(&self) -> Option<Checkpoint<N>>;
+}
+
+/// Keeps every level in a vector.
+#[derive(Clone, Debug)]
+pub struct MemoryStore<N = Fp> {
+    levels: Vec<Vec<N>>,
+    checkpoint: Option<Checkpoint<N>>,
+}
+
+impl<N> MemoryStore<N> {
+    pub fn new() -> Self {
+        MemoryStore { levels: vec![], checkpoint: None }
+    }
+}
+
+impl<N> Default for MemoryStore<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes `node` into `levels`, growing them as needed.
+///
+/// Rejects levels no tree of [`MAX_DEPTH`] has and nodes not written from the left.
+fn set_node<N: Clone>(levels: &mut Vec<Vec<N>>, level: usize, index: usize, node: N) -> Result<(), Error> {
+    if level >= MAX_DEPTH {
+        return Err(Error::CorruptStore("node above the deepest tree"));
+    }
+    if index > levels.get(level).map_or(0, |nodes| nodes.len()) {
+        return Err(Error::CorruptStore("nodes must be written from the left"));
+    }
+
+    if levels.len() <= level {
+        levels.resize(level + 1, Vec::new());
+    }
+
+    let nodes = &mut levels[level];
+    if index == nodes.len() {
+        nodes.push(node);
+    } else {
+        nodes[index] = node;
+    }
+    Ok(())
+}
+
+/// Drops the nodes of `level` from `len` on.
+fn truncate_level<N>(levels: &mut [Vec<N>], level: usize, len: usize) {
+    if let Some(nodes) = levels.get_mut(level) {
+        nodes.truncate(len);
+    }
+}
+
+impl<N: TreeNode> NodeStore<N> for MemoryStore<N> {
+    fn get(&self, level: usize, index: usize) -> Option<N> {
+        self.levels.get(level).and_then(|nodes| nodes.get(index)).copied()
+    }
+
+    fn set(&mut self, level: usize, index: usize, node: N) -> Result<(), Error> {
+        set_node(&mut self.levels, level, index, node)
+    }
+
+    fn truncate(&mut self, level: usize, len: usize) -> Result<(), Error> {
+        truncate_level(&mut self.levels, level, len);
+        Ok(())
+    }
+
+    fn len(&self, level: usize) -> usize {
+        self.levels.get(level).map_or(0, |nodes| nodes.len())
+    }
+
+    fn checkpoint(&mut self, checkpoint: &Checkpoint<N>) -> Result<(), Error> {
+        self.checkpoint = Some(checkpoint.clone());
+        Ok(())
+    }
+
+    fn last_checkpoint(&self) -> Option<Checkpoint<N>> {
+        self.checkpoint.clone()
+    }
+}
+
+const LOG_FILE: &str = "nodes.log";
+const CHECKPOINT_FILE: &str = "checkpoint";
+const CHECKPOINT_MAGIC: &[u8; 4] = b"RLNT";
+const SNAPSHOT_MAGIC: &[u8; 4] = b"RLNS";
+
+// level as u32, index as u64 and the node, all little-endian
+const RECORD_LEN: usize = 4 + 8 + 32;
+// set in the level of a record truncating the level to the index of the record
+const TRUNCATE_FLAG: u32 = 1 << 31;
+// magic, depth as u32, leaves as u64, root, snapshot generation as u64, snapshot
+// digest and log length as u64
+const CHECKPOINT_LEN: usize = 4 + 4 + 8 + 32 + 8 + 32 + 8;
+
+/// Keeps the nodes in a directory: a snapshot of every level, an append-only log of
+/// the writes since that snapshot and a checkpoint file.
+///
+/// Every write and truncation appends a record to the log. A checkpoint flushes the log and records
+/// how much of it belongs to the checkpointed tree; records after that are dropped
+/// when the store is reopened. Once the log holds more records than the snapshot holds
+/// nodes, a checkpoint writes a new snapshot instead and empties the log, so reopening
+/// reads about one record per node. All nodes are also cached in memory.
+#[derive(Debug)]
+pub struct FileStore<N = Fp> {
+    dir: PathBuf,
+    log: BufWriter<File>,
+    log_len: u64,
+    levels: Vec<Vec<N>>,
+    checkpoint: Option<Checkpoint<N>>,
+    // generation 0 is the empty snapshot, which has no file
+    generation: u64,
+    digest: [u8; 32],
+    snapshot_nodes: usize,
+    // nodes at the start of each level not written since the snapshot
+    snapshot_lens: Vec<usize>,
+}
+
+fn read_node<N: TreeNode>(bytes: &[u8]) -> Result<N, Error> {
+    let mut encoded = [0u8; 32];
+    encoded.copy_from_slice(bytes);
+    N::from_bytes(&encoded).ok_or(Error::CorruptStore("non-canonical node encoding"))
+}
+
+fn snapshot_file(generation: u64) -> String {
+    format!("nodes.{}.snapshot", generation)
+}
+
+/// Marks the nodes of `level` from `index` on as written since the snapshot.
+fn mark_written(snapshot_lens: &mut [usize], level: usize, index: usize) {
+    if let Some(len) = snapshot_lens.get_mut(level) {
+        *len = (*len).min(index);
+    }
+}
+
+/// Writes `bytes` to `path` through a temporary file, so a crash leaves the previous file.
+fn replace_file(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let mut name = path.file_name().expect("store files have a name").to_os_string();
+    name.push(".tmp");
+    let tmp = path.with_file_name(name);
+
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_data()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Writes every level to `path`, returning the digest of the snapshot.
+fn write_snapshot<N: TreeNode>(path: &Path, levels: &[Vec<N>]) -> Result<[u8; 32], Error> {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(SNAPSHOT_MAGIC);
+    bytes.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+    for nodes in levels {
+        bytes.extend_from_slice(&(nodes.len() as u64).to_le_bytes());
+    }
+    for node in levels.iter().flatten() {
+        bytes.extend_from_slice(&node.to_bytes());
+    }
+
+    replace_file(path, &bytes)?;
+    Ok(Sha256::digest(&bytes).into())
+}
+
+/// Reads the levels of the snapshot at `path`, which must hash to `digest`.
+fn read_snapshot<N: TreeNode>(path: &Path, digest: &[u8; 32]) -> Result<Vec<Vec<N>>, Error> {
+    let bytes = fs::read(path)?;
+    if Sha256::digest(&bytes).as_slice() != digest {
+        return Err(Error::CorruptStore("snapshot does not match the checkpoint"));
+    }
+
+    if bytes.len() < 8 || &bytes[..4] != SNAPSHOT_MAGIC {
+        return Err(Error::CorruptStore("unreadable snapshot"));
+    }
+    let mut count = [0u8; 4];
+    count.copy_from_slice(&bytes[4..8]);
+    let count = u32::from_le_bytes(count) as usize;
+    if count > MAX_DEPTH || bytes.len() < 8 + 8 * count {
+        return Err(Error::CorruptStore("unreadable snapshot"));
+    }
+
+    let mut offset = 8 + 8 * count;
+    let mut levels = Vec::with_capacity(count);
+    for level in 0..count {
+        let mut len = [0u8; 8];
+        len.copy_from_slice(&bytes[8 + 8 * level..16 + 8 * level]);
+        let len = u64::from_le_bytes(len) as usize;
+
+        let end = match len.checked_mul(32).and_then(|size| size.checked_add(offset)) {
+            Some(end) if end <= bytes.len() => end,
+            _ => return Err(Error::CorruptStore("unreadable snapshot")),
+        };
+        let nodes = bytes[offset..end].chunks(32).map(read_node).collect::<Result<Vec<N>, Error>>()?;
+        levels.push(nodes);
+        offset = end;
+    }
+    if offset != bytes.len() {
+        return Err(Error::CorruptStore("unreadable snapshot"));
+    }
+    Ok(levels)
+}
+
+struct StoredCheckpoint<N> {
+    checkpoint: Checkpoint<N>,
+    generation: u64,
+    digest: [u8; 32],
+    log_len: u64,
+}
+
+fn read_checkpoint<N: TreeNode>(path: &Path) -> Result<Option<StoredCheckpoint<N>>, Error> {
+    let mut bytes = vec![];
+    match File::open(path) {
+        Ok(mut file) => file.read_to_end(&mut bytes)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    if bytes.len() != CHECKPOINT_LEN || &bytes[..4] != CHECKPOINT_MAGIC {
+        return Err(Error::CorruptStore("unreadable checkpoint"));
+    }
+
+    let mut depth = [0u8; 4];
+    depth.copy_from_slice(&bytes[4..8]);
+    let mut leaves = [0u8; 8];
+    leaves.copy_from_slice(&bytes[8..16]);
+    let root = read_node(&bytes[16..48])?;
+    let mut generation = [0u8; 8];
+    generation.copy_from_slice(&bytes[48..56]);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&bytes[56..88]);
+    let mut log_len = [0u8; 8];
+    log_len.copy_from_slice(&bytes[88..96]);
+
+    let checkpoint = Checkpoint {
+        depth: u32::from_le_bytes(depth) as usize,
+        leaves: u64::from_le_bytes(leaves) as usize,
+        root,
+    };
+    Ok(Some(StoredCheckpoint {
+        checkpoint,
+        generation: u64::from_le_bytes(generation),
+        digest,
+        log_len: u64::from_le_bytes(log_len),
+    }))
+}
+
+impl<N: TreeNode> FileStore<N> {
+    /// Opens the store in `dir`, creating it if needed, at its last checkpoint.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let (checkpoint, generation, digest, log_len) = match read_checkpoint(&dir.join(CHECKPOINT_FILE))? {
+            Some(stored) => (Some(stored.checkpoint), stored.generation, stored.digest, stored.log_len),
+            None => (None, 0, [0u8; 32], 0),
+        };
+
+        let mut levels = match generation {
+            0 => vec![],
+            _ => read_snapshot(&dir.join(snapshot_file(generation)), &digest)?,
+        };
+        let mut snapshot_lens: Vec<usize> = levels.iter().map(|nodes| nodes.len()).collect();
+        let snapshot_nodes = snapshot_lens.iter().sum();
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(dir.join(LOG_FILE))?;
+
+        if file.metadata()?.len() < log_len || log_len % RECORD_LEN as u64 != 0 {
+            return Err(Error::CorruptStore("node log does not match the checkpoint"));
+        }
+
+        let mut record = [0u8; RECORD_LEN];
+        for _ in 0..log_len / RECORD_LEN as u64 {
+            file.read_exact(&mut record)?;
+
+            let mut level = [0u8; 4];
+            level.copy_from_slice(&record[..4]);
+            let mut index = [0u8; 8];
+            index.copy_from_slice(&record[4..12]);
+            let (level, index) = (u32::from_le_bytes(level), u64::from_le_bytes(index) as usize);
+
+            if level & TRUNCATE_FLAG != 0 {
+                let level = (level ^ TRUNCATE_FLAG) as usize;
+                truncate_level(&mut levels, level, index);
+                mark_written(&mut snapshot_lens, level, index);
+            } else {
+                set_node(&mut levels, level as usize, index, read_node(&record[12..])?)?;
+                mark_written(&mut snapshot_lens, level as usize, index);
+            }
+        }
+
+        // anything after the checkpoint was never committed
+        file.set_len(log_len)?;
+        file.seek(SeekFrom::Start(log_len))?;
+
+        Ok(FileStore {
+            dir,
+            log: BufWriter::new(file),
+            log_len,
+            levels,
+            checkpoint,
+            generation,
+            digest,
+            snapshot_nodes,
+            snapshot_lens,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn write_record(&mut self, level: u32, index: usize, node: &[u8; 32]) -> Result<(), Error> {
+        self.log.write_all(&level.to_le_bytes())?;
+        self.log.write_all(&(index as u64).to_le_bytes())?;
+        self.log.write_all(node)?;
+        self.log_len += RECORD_LEN as u64;
+        Ok(())
+    }
+}
+
+impl<N: TreeNode> NodeStore<N> for FileStore<N> {
+    fn get(&self, level: usize, index: usize) -> Option<N> {
+        self.levels.get(level).and_then(|nodes| nodes.get(index)).copied()
+    }
+
+    fn set(&mut self, level: usize, index: usize, node: N) -> Result<(), Error> {
+        set_node(&mut self.levels, level, index, node)?;
+        mark_written(&mut self.snapshot_lens, level, index);
+
+        self.write_record(level as u32, index, &node.to_bytes())
+    }
+
+    fn truncate(&mut self, level: usize, len: usize) -> Result<(), Error> {
+        truncate_level(&mut self.levels, level, len);
+        mark_written(&mut self.snapshot_lens, level, len);
+        self.write_record(level as u32 | TRUNCATE_FLAG, len, &[0u8; 32])
+    }
+
+    fn len(&self, level: usize) -> usize {
+        self.levels.get(level).map_or(0, |nodes| nodes.len())
+    }
+
+    fn snapshot_len(&self, level: usize) -> usize {
+        self.snapshot_lens.get(level).copied().unwrap_or(0)
+    }
+
+    fn checkpoint(&mut self, checkpoint: &Checkpoint<N>) -> Result<(), Error> {
+        self.log.flush()?;
+
+        // past one record per node, rewriting every node is cheaper to read back
+        let compact = self.log_len / RECORD_LEN as u64 > self.snapshot_nodes as u64;
+        let (generation, digest, log_len) = if compact {
+            let generation = self.generation + 1;
+            let digest = write_snapshot(&self.dir.join(snapshot_file(generation)), &self.levels)?;
+            (generation, digest, 0)
+        } else {
+            self.log.get_ref().sync_data()?;
+            (self.generation, self.digest, self.log_len)
+        };
+
+        let mut bytes = Vec::with_capacity(CHECKPOINT_LEN);
+        bytes.extend_from_slice(CHECKPOINT_MAGIC);
+        bytes.extend_from_slice(&(checkpoint.depth as u32).to_le_bytes());
+        bytes.extend_from_slice(&(checkpoint.leaves as u64).to_le_bytes());
+        bytes.extend_from_slice(&checkpoint.root.to_bytes());
+        bytes.extend_from_slice(&generation.to_le_bytes());
+        bytes.extend_from_slice(&digest);
+        bytes.extend_from_slice(&log_len.to_le_bytes());
+        replace_file(&self.dir.join(CHECKPOINT_FILE), &bytes)?;
+
+        if compact {
+            // the records are in the snapshot now, the checkpoint no longer reads them
+            self.log.get_ref().set_len(0)?;
+            self.log.seek(SeekFrom::Start(0))?;
+            self.log_len = 0;
+            if self.generation > 0 {
+                fs::remove_file(self.dir.join(snapshot_file(self.generation)))?;
+            }
+
+            self.generation = generation;
+            self.digest = digest;
+            self.snapshot_lens = self.levels.iter().map(|nodes| nodes.len()).collect();
+            self.snapshot_nodes = self.snapshot_lens.iter().sum();
+        }
+
+        self.checkpoint = Some(checkpoint.clone());
+        Ok(())
+    }
+
+    fn last_checkpoint(&self) -> Option<Checkpoint<N>> {
+        self.checkpoint.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{snapshot_file, Checkpoint, FileStore, MemoryStore, NodeStore, CHECKPOINT_FILE, LOG_FILE, RECORD_LEN};
+    use crate::merkle::{IncrementalTree, MAX_DEPTH};
+    use crate::merkle::hasher::PoseidonHasher;
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use ff::Field;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rln-{}-{}-{}", name, std::process::id(), rand::random::<u64>()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn reopen() {
+        let mut rng = rand::thread_rng();
+        let dir = temp_dir("reopen");
+
+        let leaves: Vec<Fp> = (0..13).map(|_| Fp::random(&mut rng)).collect();
+        let mut reference = IncrementalTree::new(Fp::zero(), 8);
+
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 8).unwrap();
+        for leaf in leaves[..10].iter() {
+            tree.insert(*leaf);
+            reference.insert(*leaf);
+        }
+        tree.delete(4).unwrap();
+        reference.delete(4).unwrap();
+        tree.checkpoint().unwrap();
+        let root = tree.root();
+
+        // leaves after the last checkpoint are not kept
+        for leaf in leaves[10..].iter() {
+            tree.insert(*leaf);
+        }
+        drop(tree);
+
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 8).unwrap();
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.store().len(0), 10);
+        for index in [0, 3, 9] {
+            assert_eq!(tree.witness(leaves[index]).unwrap(), reference.witness_at(index).unwrap());
+        }
+        assert!(matches!(tree.witness(leaves[4]), Err(Error::UnknownLeaf)));
+
+        tree.insert(leaves[10]);
+        reference.insert(leaves[10]);
+        assert_eq!(tree.root(), reference.root());
+
+        // truncations are replayed from the log
+        tree.truncate(7).unwrap();
+        reference.truncate(7).unwrap();
+        tree.checkpoint().unwrap();
+        drop(tree);
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 8).unwrap();
+        assert_eq!(tree.root(), reference.root());
+        assert_eq!(tree.store().len(1), 4);
+        tree.insert(leaves[7]);
+        reference.insert(leaves[7]);
+        assert_eq!(tree.root(), reference.root());
+
+        assert!(matches!(
+            IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 9),
+            Err(Error::CorruptStore(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reject_corrupt_checkpoint() {
+        let dir = temp_dir("corrupt");
+
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 4).unwrap();
+        tree.insert(Fp::from(1));
+        tree.insert(Fp::from(2));
+        tree.checkpoint().unwrap();
+        drop(tree);
+
+        // flip a bit of the stored root
+        let path = dir.join(CHECKPOINT_FILE);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[16] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(
+            IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 4),
+            Err(Error::CorruptStore(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reject_corrupt_nodes() {
+        let open = |store| IncrementalTree::<PoseidonHasher, _>::open(store, Fp::zero(), 4);
+
+        // a parent that is not the hash of its children
+        let mut store = MemoryStore::new();
+        for (level, index, node) in [(0, 0, 1), (0, 1, 2), (1, 0, 3), (2, 0, 4), (3, 0, 5)] {
+            store.set(level, index, Fp::from(node)).unwrap();
+        }
+        assert!(matches!(open(store), Err(Error::CorruptStore(_))));
+
+        let mut store = MemoryStore::new();
+        assert!(matches!(store.set(0, 1, Fp::one()), Err(Error::CorruptStore(_))));
+        assert!(matches!(store.set(MAX_DEPTH, 0, Fp::one()), Err(Error::CorruptStore(_))));
+        assert_eq!(store.len(0), 0);
+
+        // records of the log out of order or above the deepest tree
+        let dir = temp_dir("nodes");
+        let mut store = FileStore::open(&dir).unwrap();
+        store.set(0, 0, Fp::one()).unwrap();
+        store.checkpoint(&Checkpoint { depth: 1, leaves: 1, root: Fp::one() }).unwrap();
+        // the first checkpoint moves the record into a snapshot, the next one stays in the log
+        store.set(0, 0, Fp::from(2)).unwrap();
+        store.checkpoint(&Checkpoint { depth: 1, leaves: 1, root: Fp::from(2) }).unwrap();
+        drop(store);
+
+        let path = dir.join(LOG_FILE);
+        let record = fs::read(&path).unwrap();
+        assert_eq!(record.len(), RECORD_LEN);
+        for (offset, value) in [(0, MAX_DEPTH as u8), (4, 2)] {
+            let mut bytes = record.clone();
+            bytes[offset] = value;
+            fs::write(&path, &bytes).unwrap();
+            assert!(matches!(FileStore::<Fp>::open(&dir), Err(Error::CorruptStore(_))));
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compaction() {
+        let mut rng = rand::thread_rng();
+        let dir = temp_dir("compaction");
+        let open = || IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), 8);
+
+        let leaves: Vec<Fp> = (0..20).map(|_| Fp::random(&mut rng)).collect();
+        let mut reference = IncrementalTree::new(Fp::zero(), 8);
+
+        let mut tree = open().unwrap();
+        tree.insert_batch(&leaves[..16]).unwrap();
+        reference.insert_batch(&leaves[..16]).unwrap();
+        tree.checkpoint().unwrap();
+
+        // the first checkpoint moves every node into a snapshot
+        assert_eq!(fs::metadata(dir.join(LOG_FILE)).unwrap().len(), 0);
+        assert!(dir.join(snapshot_file(1)).exists());
+
+        // later writes stay in the log while it is smaller than the snapshot
+        tree.insert(leaves[16]);
+        reference.insert(leaves[16]);
+        tree.checkpoint().unwrap();
+        drop(tree);
+
+        let path = dir.join(LOG_FILE);
+        let log = fs::read(&path).unwrap();
+        assert_eq!(log.len(), 8 * RECORD_LEN);
+
+        let tree = open().unwrap();
+        assert_eq!(tree.root(), reference.root());
+        assert_eq!(tree.store().snapshot_len(0), 16);
+        assert_eq!(tree.store().snapshot_len(1), 8);
+        drop(tree);
+
+        // nodes written after the snapshot are checked when the tree is opened
+        let mut corrupt = log.clone();
+        corrupt[RECORD_LEN + 12] ^= 1;
+        fs::write(&path, &corrupt).unwrap();
+        assert!(matches!(open(), Err(Error::CorruptStore(_))));
+        fs::write(&path, &log).unwrap();
+
+        // the snapshot must match the digest in the checkpoint
+        let snapshot_path = dir.join(snapshot_file(1));
+        let snapshot = fs::read(&snapshot_path).unwrap();
+        let mut corrupt = snapshot.clone();
+        corrupt[8 + 8 * 8] ^= 1;
+        fs::write(&snapshot_path, &corrupt).unwrap();
+        assert!(matches!(FileStore::<Fp>::open(&dir), Err(Error::CorruptStore(_))));
+        fs::write(&snapshot_path, &snapshot).unwrap();
+
+        // once the log outgrows the snapshot the next checkpoint writes a new one
+        let mut tree = open().unwrap();
+        for index in 0..5 {
+            tree.update(index, leaves[17 + index % 3]).unwrap();
+            reference.update(index, leaves[17 + index % 3]).unwrap();
+        }
+        tree.checkpoint().unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        assert!(!dir.join(snapshot_file(1)).exists());
+        assert!(dir.join(snapshot_file(2)).exists());
+        drop(tree);
+
+        let tree = open().unwrap();
+        assert_eq!(tree.root(), reference.root());
+        assert_eq!(tree.store().snapshot_len(0), 17);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,466 @@
+/*
+Rebuilds the membership tree from the ordered events a registry publishes.
+
+A registration carries the index the registry gave to the member, which must be the
+next free one: a higher index means events were missed, a lower one that the history
+of the registry changed under the synced tree. An event may also carry the root the
+registry had after applying it, which must match the local one. On a conflict the sync
+is rolled back to a checkpoint and the log is replayed from there. Rolling back undoes
+the dropped events in place, so the tree may be kept in any store, and restores the
+roots accepted after the events that are kept.
+
+The event log is a text file with one event per line:
+
+register <index> <commitment> [<root>]
+remove <index> [<root>]
+
+Field elements are the hex of their 32-byte encoding. Empty lines and lines starting
+with `#` are skipped.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use super::hasher::{PoseidonHasher, TreeHasher, TreeNode};
+use crate::utils::{from_hex, to_hex};
+use super::store::{MemoryStore, NodeStore};
+use super::{IncrementalTree, RootHistory};
+use std::io;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MembershipEvent {
+    /// A member registered the commitment at the index.
+    Register(usize, Fp),
+    /// The member at the index was removed.
+    Remove(usize),
+}
+
+/// An event of the log with the root the registry published after it, if any.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoggedEvent {
+    pub event: MembershipEvent,
+    pub root: Option<Fp>,
+}
+
+/// State of the sync after some events of the log.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyncCheckpoint {
+    /// Number of events applied, which is where the log is resumed from.
+    pub events: usize,
+    pub leaves: usize,
+    pub root: Fp,
+}
+
+/// Membership tree kept in sync with an event log.
+pub struct TreeSync<H: TreeHasher<Node = Fp> = PoseidonHasher, S: NodeStore<Fp> = MemoryStore> {
+    tree: IncrementalTree<H, S>,
+    zero_value: Fp,
+    // applied events, in order, with the root and number of leaves after each
+    events: Vec<(MembershipEvent, Fp, usize)>,
+    // root of the tree before the first event
+    initial_root: Fp,
+    checkpoints: Vec<SyncCheckpoint>,
+    members: usize,
+}
+
+impl TreeSync {
+    /// Starts from an empty in-memory tree whose empty slots hold `zero_value`.
+    pub fn new(zero_value: Fp, depth: usize) -> Result<Self, Error> {
+        Self::open(MemoryStore::new(), zero_value, depth)
+    }
+}
+
+impl<H: TreeHasher<Node = Fp>, S: NodeStore<Fp>> TreeSync<H, S> {
+    /// Starts from the tree in `store`, which must be empty since the applied events
+    /// are not stored.
+    pub fn open(store: S, zero_value: Fp, depth: usize) -> Result<Self, Error> {
+        let tree = IncrementalTree::open(store, zero_value, depth)?;
+        if !tree.is_empty() {
+            return Err(Error::CorruptStore("sync must start from an empty tree"));
+        }
+
+        Ok(TreeSync {
+            initial_root: tree.root(),
+            tree,
+            zero_value,
+            events: vec![],
+            checkpoints: vec![],
+            members: 0,
+        })
+    }
+
+    /// Applies the events of `log` after the ones already applied.
+    ///
+    /// The applied events must be a prefix of `log`, otherwise the log was rewritten
+    /// and [`Error::Reorg`] holds the first position that differs. Events before a
+    /// failing one stay applied. Returns the number of events applied.
+    pub fn sync(&mut self, log: &[LoggedEvent]) -> Result<usize, Error> {
+        let applied = self.events.len();
+        if let Some(position) = (0..applied).find(|&i| log.get(i).map(|logged| logged.event) != Some(self.events[i].0)) {
+            return Err(Error::Reorg(position));
+        }
+
+        for logged in log[applied..].iter() {
+            self.apply(logged.event, logged.root)?;
+        }
+        Ok(log.len() - applied)
+    }
+
+    /// Applies the next `event`, checking the new root against `root` if given.
+    ///
+    /// Nothing changes if the event fails.
+    pub fn apply(&mut self, event: MembershipEvent, root: Option<Fp>) -> Result<(), Error> {
+        let position = self.events.len();
+        let next = self.tree.len();
+
+        match event {
+            MembershipEvent::Register(index, commitment) => {
+                if index > next {
+                    return Err(Error::EventGap(next));
+                }
+                if index < next {
+                    return Err(Error::Reorg(position));
+                }
+                self.tree.try_insert(commitment)?;
+                self.members += 1;
+            }
+            MembershipEvent::Remove(index) => {
+                if index >= next {
+                    return Err(Error::EventGap(next));
+                }
+                // a member cannot be removed twice
+                if self.tree.store().get(0, index) == Some(self.zero_value) {
+                    return Err(Error::Reorg(position));
+                }
+                self.tree.delete(index)?;
+                self.members -= 1;
+            }
+        }
+        self.events.push((event, self.tree.root(), self.tree.len()));
+
+        if matches!(root, Some(root) if root != self.tree.root()) {
+            self.rebuild(position)?;
+            return Err(Error::Reorg(position));
+        }
+
+        Ok(())
+    }
+
+    /// Records the current state as a point the sync can be rolled back to.
+    pub fn checkpoint(&mut self) -> SyncCheckpoint {
+        let checkpoint = SyncCheckpoint {
+            events: self.events.len(),
+            leaves: self.tree.len(),
+            root: self.tree.root(),
+        };
+        self.checkpoints.push(checkpoint);
+        checkpoint
+    }
+
+    pub fn checkpoints(&self) -> &[SyncCheckpoint] {
+        &self.checkpoints
+    }
+
+    /// Rolls back to the last checkpoint taken at or before the `position`-th event,
+    /// or to the empty tree if there is none, and drops the checkpoints after it.
+    ///
+    /// The log is then resumed from the `events` of the returned checkpoint.
+    pub fn rollback_to(&mut self, position: usize) -> Result<SyncCheckpoint, Error> {
+        while matches!(self.checkpoints.last(), Some(checkpoint) if checkpoint.events > position) {
+            self.checkpoints.pop();
+        }
+
+        let events = self.checkpoints.last().map_or(0, |checkpoint| checkpoint.events);
+        self.rebuild(events)?;
+
+        Ok(SyncCheckpoint {
+            events,
+            leaves: self.tree.len(),
+            root: self.tree.root(),
+        })
+    }
+
+    /// Undoes the applied events after the first `events`.
+    ///
+    /// Leaves registered by the dropped events are truncated and the ones they removed
+    /// are put back. The root history is restored to the one the tree had after the
+    /// kept events.
+    fn rebuild(&mut self, events: usize) -> Result<(), Error> {
+        let dropped = self.events.split_off(events);
+
+        let commitments: Vec<Fp> = self
+            .events
+            .iter()
+            .filter_map(|(event, _, _)| match event {
+                MembershipEvent::Register(_, commitment) => Some(*commitment),
+                MembershipEvent::Remove(_) => None,
+            })
+            .collect();
+
+        self.tree.truncate(commitments.len())?;
+        for (event, _, _) in dropped.iter() {
+            match event {
+                MembershipEvent::Remove(index) if *index < commitments.len() => {
+                    self.tree.update(*index, commitments[*index])?;
+                }
+                _ => {}
+            }
+        }
+
+        let removals = self.events.iter().filter(|(event, _, _)| matches!(event, MembershipEvent::Remove(_))).count();
+        self.members = commitments.len() - removals;
+
+        // replay the roots of the kept events, each removal revokes the roots holding its leaf
+        let mut history = RootHistory::new(self.tree.root_history().size());
+        history.push(self.initial_root, 0);
+        for (event, root, leaves) in self.events.iter() {
+            if let MembershipEvent::Remove(index) = event {
+                history.revoke(*index);
+            }
+            history.push(*root, *leaves);
+        }
+        self.tree.set_root_history(history);
+        Ok(())
+    }
+
+    pub fn tree(&self) -> &IncrementalTree<H, S> {
+        &self.tree
+    }
+
+    pub fn root(&self) -> Fp {
+        self.tree.root()
+    }
+
+    /// Number of registered members that were not removed.
+    pub fn member_count(&self) -> usize {
+        self.members
+    }
+
+    /// Number of events applied so far.
+    pub fn events_applied(&self) -> usize {
+        self.events.len()
+    }
+}
+
+fn parse_node(hex: &str) -> Option<Fp> {
+    from_hex(hex).and_then(|bytes| <Fp as TreeNode>::from_bytes(&bytes))
+}
+
+fn parse_event(line: &str) -> Option<LoggedEvent> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    let (event, rest) = match fields.as_slice() {
+        ["register", index, commitment, rest @ ..] => {
+            (MembershipEvent::Register(index.parse().ok()?, parse_node(commitment)?), rest)
+        }
+        ["remove", index, rest @ ..] => (MembershipEvent::Remove(index.parse().ok()?), rest),
+        _ => return None,
+    };
+
+    let root = match rest {
+        [] => None,
+        [root] => Some(parse_node(root)?),
+        _ => return None,
+    };
+
+    Some(LoggedEvent { event, root })
+}
+
+/// Reads an event log, failing with the number of the first line that cannot be parsed.
+pub fn read_events<R: io::BufRead>(reader: R) -> Result<Vec<LoggedEvent>, Error> {
+    let mut events = vec![];
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        events.push(parse_event(line).ok_or(Error::InvalidEvent(number + 1))?);
+    }
+    Ok(events)
+}
+
+/// Appends `logged` to an event log.
+pub fn write_event<W: io::Write>(writer: &mut W, logged: &LoggedEvent) -> Result<(), Error> {
+    match logged.event {
+        MembershipEvent::Register(index, commitment) => {
+            write!(writer, "register {} {}", index, to_hex(&commitment.to_bytes()))?
+        }
+        MembershipEvent::Remove(index) => write!(writer, "remove {}", index)?,
+    }
+    if let Some(root) = logged.root {
+        write!(writer, " {}", to_hex(&root.to_bytes()))?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_events, write_event, LoggedEvent, MembershipEvent, TreeSync};
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use crate::merkle::IncrementalTree;
+    use crate::merkle::hasher::{PoseidonHasher, TreeHasher};
+    use crate::merkle::store::{FileStore, NodeStore};
+    use ff::Field;
+    use rand::Rng;
+    use std::fs::{self, File};
+    use std::io::{BufReader, BufWriter, Write};
+
+    const DEPTH: usize = 10;
+
+    /// Random registrations and removals, each with the root of the registry after it.
+    fn registry_events(count: usize) -> Vec<LoggedEvent> {
+        let mut rng = rand::thread_rng();
+        let mut registry = IncrementalTree::new(Fp::zero(), DEPTH);
+        let mut members = vec![];
+
+        (0..count)
+            .map(|_| {
+                let event = if !members.is_empty() && rng.gen_bool(0.2) {
+                    let index = members.swap_remove(rng.gen_range(0..members.len()));
+                    registry.delete(index).unwrap();
+                    MembershipEvent::Remove(index)
+                } else {
+                    let index = registry.len();
+                    let commitment = Fp::random(&mut rng);
+                    registry.insert(commitment);
+                    members.push(index);
+                    MembershipEvent::Register(index, commitment)
+                };
+                LoggedEvent { event, root: Some(registry.root()) }
+            })
+            .collect()
+    }
+
+    fn member_count(events: &[LoggedEvent]) -> usize {
+        events.iter().fold(0, |count, logged| match logged.event {
+            MembershipEvent::Register(..) => count + 1,
+            MembershipEvent::Remove(_) => count - 1,
+        })
+    }
+
+    /// Whether `sync` has the root history of a fresh sync of `events`.
+    fn has_history_of<H: TreeHasher<Node = Fp>, S: NodeStore<Fp>>(sync: &TreeSync<H, S>, events: &[LoggedEvent]) -> bool {
+        let mut fresh = TreeSync::new(Fp::zero(), DEPTH).unwrap();
+        fresh.sync(events).unwrap();
+        sync.tree().root_history().iter().eq(fresh.tree().root_history().iter())
+    }
+
+    #[test]
+    fn sync_from_file() {
+        let path = std::env::temp_dir().join(format!("rln-events-{}-{}", std::process::id(), rand::random::<u64>()));
+        let events = registry_events(60);
+
+        let mut writer = BufWriter::new(File::create(&path).unwrap());
+        writeln!(writer, "# registry events").unwrap();
+        for logged in events.iter() {
+            write_event(&mut writer, logged).unwrap();
+        }
+        drop(writer);
+
+        let log = read_events(BufReader::new(File::open(&path).unwrap())).unwrap();
+        assert_eq!(log, events);
+
+        // resume from a partial log
+        let mut sync = TreeSync::new(Fp::zero(), DEPTH).unwrap();
+        assert_eq!(sync.sync(&log[..25]).unwrap(), 25);
+        assert_eq!(sync.root(), log[24].root.unwrap());
+        assert_eq!(sync.sync(&log).unwrap(), 35);
+        assert_eq!(sync.sync(&log).unwrap(), 0);
+
+        assert_eq!(sync.root(), log[59].root.unwrap());
+        assert_eq!(sync.member_count(), member_count(&log));
+        assert_eq!(sync.events_applied(), 60);
+
+        // the same log synced into a file store, rolled back and resumed
+        let dir = std::env::temp_dir().join(format!("rln-sync-{}-{}", std::process::id(), rand::random::<u64>()));
+        let mut stored = TreeSync::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), DEPTH).unwrap();
+        stored.sync(&log[..30]).unwrap();
+        stored.checkpoint();
+        stored.sync(&log[..45]).unwrap();
+        assert_eq!(stored.rollback_to(40).unwrap().events, 30);
+        assert_eq!(stored.root(), log[29].root.unwrap());
+        assert!(has_history_of(&stored, &log[..30]));
+        stored.sync(&log).unwrap();
+        assert_eq!(stored.root(), sync.root());
+        drop(stored);
+        fs::remove_dir_all(&dir).unwrap();
+
+        // a sync does not start from a store that already holds leaves
+        let mut tree = IncrementalTree::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), DEPTH).unwrap();
+        tree.insert(Fp::one());
+        tree.checkpoint().unwrap();
+        drop(tree);
+        assert!(matches!(
+            TreeSync::<PoseidonHasher, _>::open(FileStore::open(&dir).unwrap(), Fp::zero(), DEPTH),
+            Err(Error::CorruptStore(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+
+        fs::write(&path, "register 0 zz\n").unwrap();
+        assert!(matches!(read_events(BufReader::new(File::open(&path).unwrap())), Err(Error::InvalidEvent(1))));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn gaps_and_reorgs() {
+        let events = registry_events(30);
+
+        let mut sync = TreeSync::new(Fp::zero(), DEPTH).unwrap();
+        sync.sync(&events[..10]).unwrap();
+        let checkpoint = sync.checkpoint();
+        sync.sync(&events[..20]).unwrap();
+        let (root, members) = (sync.root(), sync.member_count());
+
+        // skipped registration
+        let gap = MembershipEvent::Register(sync.tree().len() + 1, Fp::one());
+        assert!(matches!(sync.apply(gap, None), Err(Error::EventGap(index)) if index == sync.tree().len()));
+
+        // registration at a taken index
+        assert!(matches!(sync.apply(MembershipEvent::Register(0, Fp::one()), None), Err(Error::Reorg(20))));
+
+        // published root does not match
+        let next = MembershipEvent::Register(sync.tree().len(), Fp::one());
+        assert!(matches!(sync.apply(next, Some(Fp::one())), Err(Error::Reorg(20))));
+        assert_eq!((sync.root(), sync.member_count(), sync.events_applied()), (root, members, 20));
+        assert!(has_history_of(&sync, &events[..20]));
+
+        // the registry rewrites its history after the 15th event
+        let mut rewritten = events[..15].to_vec();
+        let mut registry = IncrementalTree::new(Fp::zero(), DEPTH);
+        for logged in rewritten.iter() {
+            match logged.event {
+                MembershipEvent::Register(_, commitment) => registry.insert(commitment),
+                MembershipEvent::Remove(index) => registry.delete(index).unwrap(),
+            }
+        }
+        for _ in 0..10 {
+            let index = registry.len();
+            let commitment = Fp::random(&mut rand::thread_rng());
+            registry.insert(commitment);
+            rewritten.push(LoggedEvent { event: MembershipEvent::Register(index, commitment), root: Some(registry.root()) });
+        }
+
+        let position = match sync.sync(&rewritten) {
+            Err(Error::Reorg(position)) => position,
+            other => panic!("expected a reorg, got {:?}", other.map(|_| ())),
+        };
+        assert_eq!(position, 15);
+
+        assert_eq!(sync.rollback_to(position).unwrap(), checkpoint);
+        assert_eq!(sync.root(), checkpoint.root);
+        assert_eq!(sync.member_count(), member_count(&events[..10]));
+        assert!(has_history_of(&sync, &events[..10]));
+        assert!(!sync.tree().is_acceptable_root(&events[19].root.unwrap()));
+
+        assert_eq!(sync.sync(&rewritten).unwrap(), 15);
+        assert_eq!(sync.root(), registry.root());
+        assert_eq!(sync.member_count(), member_count(&rewritten));
+
+        // no checkpoint left before the first event
+        assert_eq!(sync.rollback_to(5).unwrap().leaves, 0);
+        assert!(sync.checkpoints().is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+/*
+Path of a single member, kept up to date from insertion events instead of the full tree.
+
+When a leaf is appended at index `j`, the only sibling of the member at index `i` that
+changes is the one on the level where the paths of `i` and `j` meet, the highest bit
+in which they differ. That sibling is the subtree holding the new leaf, whose value
+follows from the leaf, the zero subtrees right of it and the filled subtrees left of
+it. The insertion event carries those left subtrees, the frontier of the tree before
+the insertion, so updating a member costs at most two hashes per level. The event also
+carries the root published after the insertion, which the updated path must reach.
+
+Removals and updates of other leaves are not followed: a member whose subtree changed
+that way has to fetch a fresh path.
+*/
+use crate::halo2::pasta::Fp;
+use crate::error::Error;
+use super::hasher::{PoseidonHasher, TreeHasher};
+use super::proof::MerkleProof;
+use super::MAX_DEPTH;
+use std::marker::PhantomData;
+
+/// A leaf appended to the tree, with what a member needs to follow it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Insertion<N = Fp> {
+    pub index: usize,
+    pub leaf: N,
+    /// Left siblings on the path of the new leaf, from the leaf level up, one for each
+    /// level where the new leaf is a right child.
+    pub frontier: Vec<N>,
+    /// Root of the tree after the insertion, as published by the registry.
+    pub root: N,
+}
+
+/// Authentication path of one leaf, following later insertions.
+#[derive(Clone, Debug)]
+pub struct MemberWitness<H: TreeHasher = PoseidonHasher> {
+    index: usize,
+    leaf: H::Node,
+    siblings: Vec<H::Node>,
+    zeroes: Vec<H::Node>,
+    root: H::Node,
+    leaves: usize,
+    _marker: PhantomData<H>,
+}
+
+impl<H: TreeHasher> MemberWitness<H> {
+    /// Starts from the `proof` of `leaf` in a tree holding `leaves` leaves.
+    pub fn new(zero_value: H::Node, leaf: H::Node, proof: &MerkleProof<H::Node>, leaves: usize) -> Result<Self, Error> {
+        let depth = proof.depth();
+        if depth > MAX_DEPTH {
+            return Err(Error::DepthTooLarge(depth));
+        }
+
+        let index = proof.leaf_index as usize;
+        if index >= leaves {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+
+        let mut zeroes = vec![zero_value];
+        for level in 0..depth {
+            zeroes.push(H::hash_layer(zeroes[level], zeroes[level]));
+        }
+
+        let mut witness = MemberWitness {
+            index,
+            leaf,
+            siblings: proof.siblings.clone(),
+            zeroes,
+            root: zero_value,
+            leaves,
+            _marker: PhantomData,
+        };
+        witness.root = MerkleProof::new(index as u64, witness.siblings.clone())?.root_with::<H>(leaf);
+        Ok(witness)
+    }
+
+    /// Updates the path and the root with the next appended leaf.
+    ///
+    /// Nothing changes if the insertion is not the next one, its frontier does not
+    /// match its index or the updated path does not reach the published root.
+    pub fn apply(&mut self, insertion: &Insertion<H::Node>) -> Result<(), Error> {
+        let depth = self.depth();
+        let index = insertion.index;
+
+        if index != self.leaves {
+            return Err(Error::EventGap(self.leaves));
+        }
+        if index as u64 >= 1u64 << depth {
+            return Err(Error::TreeFull);
+        }
+        if insertion.leaf == self.zeroes[0] {
+            return Err(Error::ZeroLeaf);
+        }
+        if insertion.frontier.len() != index.count_ones() as usize {
+            return Err(Error::InvalidMerkleProof("frontier does not match the index"));
+        }
+
+        // level where the paths meet, the new subtree is the sibling of the member's node there
+        let meet = (usize::BITS - (self.index ^ index).leading_zeros() - 1) as usize;
+
+        let mut frontier = insertion.frontier.iter();
+        let mut node = insertion.leaf;
+        for level in 0..meet {
+            node = if (index >> level) & 1 == 1 {
+                H::hash_layer(*frontier.next().unwrap(), node)
+            } else {
+                H::hash_layer(node, self.zeroes[level])
+            };
+        }
+
+        let mut siblings = self.siblings.clone();
+        siblings[meet] = node;
+        let root = MerkleProof::new(self.index as u64, siblings.clone())?.root_with::<H>(self.leaf);
+        if root != insertion.root {
+            return Err(Error::InvalidMerkleProof("insertion does not lead to the published root"));
+        }
+
+        self.siblings = siblings;
+        self.leaves += 1;
+        self.root = root;
+        Ok(())
+    }
+
+    /// The current path as a [`MerkleProof`].
+    pub fn proof(&self) -> MerkleProof<H::Node> {
+        MerkleProof::new(self.index as u64, self.siblings.clone()).expect("index fits the depth")
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn leaf(&self) -> H::Node {
+        self.leaf
+    }
+
+    /// Siblings from the leaf level up.
+    pub fn siblings(&self) -> &[H::Node] {
+        &self.siblings
+    }
+
+    pub fn root(&self) -> H::Node {
+        self.root
+    }
+
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Number of leaves in the tree the path is for.
+    pub fn leaves(&self) -> usize {
+        self.leaves
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Insertion, MemberWitness};
+    use crate::halo2::pasta::Fp;
+    use crate::error::Error;
+    use crate::merkle::IncrementalTree;
+    use crate::merkle::hasher::{PoseidonHasher, Sha256Hasher};
+    use crate::merkle::store::MemoryStore;
+    use ff::Field;
+    use rand::Rng;
+
+    #[test]
+    fn follow_insertions() {
+        let mut rng = rand::thread_rng();
+        let depth = 12;
+        let mut tree = IncrementalTree::new(Fp::zero(), depth);
+
+        let mut members: Vec<MemberWitness> = vec![];
+        for _ in 0..3000 {
+            let leaf = Fp::random(&mut rng);
+            tree.insert(leaf);
+
+            let insertion = tree.insertion_at(tree.len() - 1).unwrap();
+            for member in members.iter_mut() {
+                member.apply(&insertion).unwrap();
+            }
+
+            // members join at random positions, the first leaf included
+            if members.is_empty() || rng.gen_bool(0.002) {
+                let proof = tree.proof_at(tree.len() - 1).unwrap();
+                members.push(MemberWitness::new(Fp::zero(), leaf, &proof, tree.len()).unwrap());
+            }
+
+            for member in members.iter() {
+                let (siblings, _) = tree.witness_at(member.index()).unwrap();
+                assert_eq!(member.siblings(), &siblings[..]);
+                assert_eq!(member.root(), tree.root());
+            }
+        }
+        assert!(members.len() > 1);
+
+        let member = &members[0];
+        assert!(member.proof().verify(tree.root(), member.leaf()));
+    }
+
+    #[test]
+    fn reject_invalid_insertions() {
+        let mut tree = IncrementalTree::new(Fp::zero(), 2);
+        tree.insert(Fp::from(1));
+        tree.insert(Fp::from(2));
+
+        let proof = tree.proof_at(0).unwrap();
+        let mut member = MemberWitness::<PoseidonHasher>::new(Fp::zero(), Fp::from(1), &proof, 2).unwrap();
+        assert!(matches!(
+            MemberWitness::<PoseidonHasher>::new(Fp::zero(), Fp::from(1), &proof, 0),
+            Err(Error::IndexOutOfBounds(0))
+        ));
+
+        tree.insert(Fp::from(3));
+        let insertion = tree.insertion_at(2).unwrap();
+        assert_eq!(insertion.frontier, vec![tree.witness_at(2).unwrap().0[1]]);
+
+        let skipped = Insertion { index: 3, leaf: Fp::from(4), frontier: vec![Fp::from(3)], root: tree.root() };
+        assert!(matches!(member.apply(&skipped), Err(Error::EventGap(2))));
+        let zero = Insertion { leaf: Fp::zero(), ..insertion.clone() };
+        assert!(matches!(member.apply(&zero), Err(Error::ZeroLeaf)));
+        let frontier = Insertion { frontier: vec![], ..insertion.clone() };
+        assert!(matches!(member.apply(&frontier), Err(Error::InvalidMerkleProof(_))));
+        // a leaf that does not lead to the published root
+        let forged = Insertion { leaf: Fp::from(7), ..insertion.clone() };
+        assert!(matches!(member.apply(&forged), Err(Error::InvalidMerkleProof(_))));
+        let root = Insertion { root: Fp::from(7), ..insertion.clone() };
+        assert!(matches!(member.apply(&root), Err(Error::InvalidMerkleProof(_))));
+        assert_eq!(member.leaves(), 2);
+        assert_eq!(member.proof(), proof);
+
+        member.apply(&insertion).unwrap();
+        tree.insert(Fp::from(4));
+        member.apply(&tree.insertion_at(3).unwrap()).unwrap();
+        assert_eq!(member.root(), tree.root());
+
+        let full = Insertion { index: 4, leaf: Fp::from(5), frontier: vec![Fp::from(1)], root: tree.root() };
+        assert!(matches!(member.apply(&full), Err(Error::TreeFull)));
+
+        // other hashers
+        let mut tree = IncrementalTree::<Sha256Hasher, _>::open(MemoryStore::new(), [0u8; 32], 4).unwrap();
+        tree.insert([1u8; 32]);
+        let mut member = MemberWitness::<Sha256Hasher>::new([0u8; 32], [1u8; 32], &tree.proof_at(0).unwrap(), 1).unwrap();
+        for leaf in 2..=9u8 {
+            tree.insert([leaf; 32]);
+            member.apply(&tree.insertion_at(tree.len() - 1).unwrap()).unwrap();
+        }
+        assert_eq!(member.root(), tree.root());
+        assert_eq!(member.proof(), tree.proof_at(0).unwrap());
+    }
+}
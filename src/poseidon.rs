@@ -9,6 +9,7 @@ use crate::halo2::arithmetic::FieldExt;
 
 pub(crate) mod fp;
 pub(crate) mod fq;
+pub(crate) mod fp5;
 pub(crate) mod grain;
 pub(crate) mod mds;
 
@@ -18,6 +19,9 @@ pub(crate) mod test_vectors;
 mod p128pow5t3;
 pub use p128pow5t3::P128Pow5T3;
 
+mod p128pow5t5;
+pub use p128pow5t5::P128Pow5T5;
+
 use grain::SboxType;
 
 /// The type used to hold permutation state.
@@ -0,0 +1,2519 @@
+//! Constants for Poseidon with a width of 5 over the Pallas base field, generated with
+//! the Grain LFSR in `super::grain` using the same procedure as
+//! https://github.com/daira/pasta-hadeshash
+//!
+//! $ sage generate_parameters_grain.sage 1 0 255 5 8 60 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001
+use pasta_curves::Fp;
+
+// Number of round constants: 340
+// Round constants for GF(p):
+pub(crate) const ROUND_CONSTANTS: [[Fp; 5]; 68] = [
+    [
+        Fp::from_raw([
+            0xbbe9_8b52_74b9_c8e1,
+            0x2d77_47ad_553b_028d,
+            0x769c_6fd0_d51e_9826,
+            0x3e92_829c_e321_755f,
+        ]),
+        Fp::from_raw([
+            0xe967_d352_05b8_7694,
+            0x7dbe_99bc_6a4d_1b23,
+            0xc315_ff02_0702_3212,
+            0x3d7e_5dec_cc6e_b706,
+        ]),
+        Fp::from_raw([
+            0x4098_cd66_fa12_b78b,
+            0x7127_8372_7e14_43c7,
+            0x93cc_349a_059d_752c,
+            0x1355_8f81_fbc1_5c27,
+        ]),
+        Fp::from_raw([
+            0xd671_682c_bc1a_9534,
+            0xa77f_4c50_7ea6_dd07,
+            0xf6df_9e59_08a5_4f69,
+            0x076d_f166_a42e_ae40,
+        ]),
+        Fp::from_raw([
+            0x9621_ea30_beef_d075,
+            0xa121_837b_cbe1_13d5,
+            0xa5f9_2279_4008_a263,
+            0x30be_41b5_a9d8_af19,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1040_676e_3e59_1306,
+            0xd338_12d4_44f8_8e47,
+            0x7521_0f82_0977_f7fc,
+            0x39f5_7e4c_8a11_78d8,
+        ]),
+        Fp::from_raw([
+            0x0ee6_206a_3e7b_f035,
+            0x2ee6_74c5_cb12_3889,
+            0x3648_2204_d9cd_db07,
+            0x3514_084b_13bc_0be6,
+        ]),
+        Fp::from_raw([
+            0x157a_372d_ba2e_a013,
+            0x0e2e_b742_2807_eb0c,
+            0x9747_c1be_b050_ff32,
+            0x3296_e51f_12e0_f5c4,
+        ]),
+        Fp::from_raw([
+            0xcf74_32c9_0e27_358d,
+            0xae7f_1ad8_a962_3bb7,
+            0xdd5b_589b_80a7_fac0,
+            0x3b76_246a_baf3_3b03,
+        ]),
+        Fp::from_raw([
+            0xe0be_c0ee_709f_7409,
+            0x87c1_808f_7d82_9e2e,
+            0xc883_c7ce_f72e_c48e,
+            0x0b40_e7e0_2f5c_b836,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xbefc_22ed_2232_690f,
+            0x98e5_4747_5359_b4a4,
+            0x6e88_71c0_1d03_80a6,
+            0x2ee8_1b5c_29c9_3b8a,
+        ]),
+        Fp::from_raw([
+            0x7c13_18ed_a718_f54d,
+            0x5725_2e91_5b8d_39ea,
+            0x9b74_c464_955b_a9b3,
+            0x341f_f90f_c4a8_afee,
+        ]),
+        Fp::from_raw([
+            0x5a26_6f42_77bb_2a32,
+            0xf314_5975_07d2_8c73,
+            0x9c10_9f93_0eb0_1892,
+            0x05e9_b152_bfd4_946b,
+        ]),
+        Fp::from_raw([
+            0x5020_e9e3_e2fc_909a,
+            0x94ab_00ed_5a6a_e643,
+            0x6b6f_0a1d_bbab_c083,
+            0x1589_a5cb_cee1_3b69,
+        ]),
+        Fp::from_raw([
+            0x044f_afc0_1bfe_6ae4,
+            0x8dac_6bc7_c220_4906,
+            0xb313_7f5a_90ea_1329,
+            0x0538_2ee6_ad97_381e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc5c5_52d1_1c1d_0015,
+            0x1df7_4733_6213_8a9d,
+            0x0aa8_0c71_4b7b_2a0c,
+            0x0900_bcfe_5e7c_1b7d,
+        ]),
+        Fp::from_raw([
+            0xd92c_ce3c_7e55_a7c2,
+            0x79cd_a84d_511a_04e0,
+            0x7294_40dc_08d0_256a,
+            0x0513_deb8_9d2e_48fc,
+        ]),
+        Fp::from_raw([
+            0x2b30_4b0a_9f4a_0c28,
+            0x98d8_ea48_77d5_47c6,
+            0x3a1f_fcf6_ac69_3eef,
+            0x2d83_f332_4639_c5d8,
+        ]),
+        Fp::from_raw([
+            0x1766_bb48_d7f9_6cba,
+            0x9513_4408_b666_8bbf,
+            0x29ca_4d0c_6342_8645,
+            0x16d3_a137_00ec_503e,
+        ]),
+        Fp::from_raw([
+            0x882a_8e87_149d_7244,
+            0xf40a_88fc_73d6_2150,
+            0x1253_b004_30d3_5f89,
+            0x3180_50e9_71e0_7593,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x91c6_5eb0_cd11_c0a4,
+            0xa9de_41e2_1864_16bf,
+            0x02d6_ee26_7b7c_737c,
+            0x2b83_4576_3484_d7ec,
+        ]),
+        Fp::from_raw([
+            0xd37e_ad39_2805_9117,
+            0x8592_9220_a641_8dfa,
+            0xf3c2_9c75_00c6_4e6b,
+            0x055a_a90a_a60e_f9b7,
+        ]),
+        Fp::from_raw([
+            0x1a1a_4780_053d_5817,
+            0xe8dd_0fa3_2f00_7bad,
+            0xcaaf_4338_f018_f869,
+            0x05c0_a1f1_6f97_f582,
+        ]),
+        Fp::from_raw([
+            0x5371_c796_8d9f_52cd,
+            0xc1d2_011b_7fd1_9a47,
+            0x93b9_c1d7_f849_276a,
+            0x0151_9e13_8585_91aa,
+        ]),
+        Fp::from_raw([
+            0xc93a_ce48_bdb9_dc93,
+            0x02ae_23e1_3d0f_bcce,
+            0xd410_18fe_d6f1_a291,
+            0x06b8_a128_70a1_5479,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb3ad_b4b0_939d_76e2,
+            0x2f95_dc3c_7e46_e68a,
+            0xc229_7aa1_0266_82d2,
+            0x33ed_a3c3_4737_9e61,
+        ]),
+        Fp::from_raw([
+            0xd61f_e81e_3130_b89b,
+            0xa1f6_e205_9c44_3ac7,
+            0x93a1_218a_148a_da85,
+            0x1877_2804_5111_275b,
+        ]),
+        Fp::from_raw([
+            0x77b4_52e2_e22c_4f17,
+            0xdc0d_d484_9fe9_55bb,
+            0xa03f_f543_e9a9_e5a4,
+            0x397e_c485_c5a8_b0c8,
+        ]),
+        Fp::from_raw([
+            0xaff6_64ca_a84c_d2d5,
+            0x992b_e046_8372_addb,
+            0x455d_5a65_9266_7092,
+            0x2f33_f8de_90f8_1248,
+        ]),
+        Fp::from_raw([
+            0x1494_3e7e_9246_ebca,
+            0xeb05_deae_e685_058b,
+            0xf38c_5edf_bd73_7d3c,
+            0x061a_1a45_8994_ddf9,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x344e_883a_6961_1813,
+            0x24b9_3fa3_8c5d_4541,
+            0x8be2_8955_7ddd_2ca0,
+            0x0b2d_a770_936d_6c77,
+        ]),
+        Fp::from_raw([
+            0x9408_2281_a625_0d4b,
+            0x81d7_8de7_addc_e859,
+            0x877d_4528_a4e8_0d5d,
+            0x210b_d321_7a14_1c55,
+        ]),
+        Fp::from_raw([
+            0xeda7_6f01_998a_01ea,
+            0xa623_7f06_4481_fe66,
+            0x0dc4_c343_a852_59f6,
+            0x2946_bfc0_f45c_1f1a,
+        ]),
+        Fp::from_raw([
+            0xdffe_9131_a72b_4209,
+            0x0fdc_4229_ea31_6980,
+            0x1b69_6192_44cc_2a61,
+            0x38f0_6c48_d4dc_53cb,
+        ]),
+        Fp::from_raw([
+            0xc6c6_ee5e_7fef_3e45,
+            0x9301_7213_0b3f_5476,
+            0xfe65_9e9e_bbda_832c,
+            0x3109_31f0_204c_9936,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x76ea_f8f9_7e81_d858,
+            0x5265_5111_a101_7d8c,
+            0xd563_ed3c_dcf5_5ace,
+            0x0370_a259_836b_3766,
+        ]),
+        Fp::from_raw([
+            0x0a74_9855_7fe8_7fb5,
+            0xb715_084b_25f6_e456,
+            0x9962_2af0_9d68_78c5,
+            0x36d8_869b_b69a_51ee,
+        ]),
+        Fp::from_raw([
+            0x1f0c_de3f_cb87_8394,
+            0x0de8_a46a_6d84_02f3,
+            0x2f91_2380_6872_094c,
+            0x18fa_a7f5_1e1b_7a44,
+        ]),
+        Fp::from_raw([
+            0xb4f8_0225_d82b_617b,
+            0x67f5_ddfe_7fa1_fb9f,
+            0x93e0_d6aa_7eef_dca7,
+            0x3610_d022_aacb_e585,
+        ]),
+        Fp::from_raw([
+            0x396a_3d0c_59f6_8c1c,
+            0xb361_146a_04ad_5fc7,
+            0x569b_c686_0087_b2a4,
+            0x3b5f_13d6_a8bb_ff31,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc9cd_8bdd_adc9_47f1,
+            0xa85e_2fb1_e372_5bf3,
+            0x40f8_ed52_54f2_9128,
+            0x2f54_586a_b9b7_8863,
+        ]),
+        Fp::from_raw([
+            0xffe9_1be3_87cb_e96c,
+            0x296d_6c62_0a88_5eea,
+            0x363e_5afc_050f_9fc9,
+            0x0060_6231_b689_a040,
+        ]),
+        Fp::from_raw([
+            0x89f5_1fa6_e6ee_e4a2,
+            0x7d67_623c_fd1f_cbf4,
+            0xcd07_54ab_9fdf_9ad6,
+            0x2d58_d321_20c2_5995,
+        ]),
+        Fp::from_raw([
+            0xb47a_59cd_387d_322f,
+            0x8839_ba8e_320a_104c,
+            0x8a4c_cddd_d4d8_466f,
+            0x37cb_0f65_5951_fca1,
+        ]),
+        Fp::from_raw([
+            0xa46c_27ed_3eae_5ff2,
+            0x4170_6c1f_2858_c1e8,
+            0x807d_5b5d_e772_7b4e,
+            0x0172_ffdf_ba7e_43ca,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x8e88_9421_e70e_be03,
+            0x236d_2c13_4ccf_35ef,
+            0x1e72_0a46_ef83_334a,
+            0x2216_dd90_7ab9_8c0d,
+        ]),
+        Fp::from_raw([
+            0x292d_789d_25c0_f8d5,
+            0x306d_6430_ce2b_abf7,
+            0x3607_a393_90a0_de71,
+            0x1687_09f6_68b6_35f0,
+        ]),
+        Fp::from_raw([
+            0xad14_3757_2cc6_151d,
+            0x115d_0dfe_3472_cec4,
+            0xfd35_5dea_8059_5e21,
+            0x0ff6_a382_3440_877d,
+        ]),
+        Fp::from_raw([
+            0x8ce5_4730_cd3f_e9ae,
+            0x3a87_9573_1b13_f656,
+            0xf7f4_22e3_059f_18d8,
+            0x2672_98d2_e462_27f7,
+        ]),
+        Fp::from_raw([
+            0x59e3_7ac4_7209_235a,
+            0x693a_155f_e226_4422,
+            0x0337_3441_a5b0_9b44,
+            0x1ecb_e7a6_0848_0772,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x0203_1cc3_93d4_eccb,
+            0x5b43_4ee6_319f_07e5,
+            0x77d5_f5c3_368c_5bdd,
+            0x31cb_23e6_b5d7_3935,
+        ]),
+        Fp::from_raw([
+            0xa3de_c3f1_04d2_38c0,
+            0xb1bb_429b_19f5_6894,
+            0x9190_ac48_740c_3b5a,
+            0x2260_56b5_dec9_afd1,
+        ]),
+        Fp::from_raw([
+            0xb2a0_be63_e699_07d9,
+            0x6ec4_819a_e76c_e23f,
+            0xad10_451d_ed70_d7ae,
+            0x0907_7c02_1183_dd37,
+        ]),
+        Fp::from_raw([
+            0x9899_9dd5_f460_e446,
+            0x6240_72be_5458_7e06,
+            0xaac6_532e_f919_e61c,
+            0x01d5_779b_e747_7b96,
+        ]),
+        Fp::from_raw([
+            0xbfca_fe54_4183_9d37,
+            0x2774_b594_ed23_2810,
+            0x94e7_6f7a_bdb1_9097,
+            0x1d5c_957d_a083_2d5b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x8eb7_cc86_8538_bd7a,
+            0x7bd5_5ca9_ca80_1bd8,
+            0xfc7c_e189_7aa1_29f6,
+            0x1b67_8335_a80f_d045,
+        ]),
+        Fp::from_raw([
+            0xad36_e4d3_dcdb_b7ed,
+            0x7c90_f9f5_0b9e_1ae5,
+            0x1c1c_b180_9e5b_f185,
+            0x31e6_9d70_6a5c_1e01,
+        ]),
+        Fp::from_raw([
+            0xaae8_2cbb_494c_e9e4,
+            0x2d80_c957_7919_5807,
+            0x3a35_0e78_f990_2491,
+            0x1e46_fdcb_b370_5f66,
+        ]),
+        Fp::from_raw([
+            0x7c90_e931_7fea_2c0c,
+            0x1b1e_97c2_f0fc_7e84,
+            0x2062_340e_c1fa_e17c,
+            0x02a3_caff_274f_4094,
+        ]),
+        Fp::from_raw([
+            0x181e_cbbb_212e_0f70,
+            0x7f81_0cc5_30db_1340,
+            0x8c76_7d1e_63e8_d5cc,
+            0x1b94_7041_8a07_d8c8,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x32d4_3bb5_4428_7c9d,
+            0xd2a7_3e1a_44ed_c43e,
+            0x9bf0_cc72_1747_7d81,
+            0x3d5d_0489_e273_62db,
+        ]),
+        Fp::from_raw([
+            0x8873_6313_7baa_09ab,
+            0x999d_66a5_753e_b4b9,
+            0x2f9a_ce56_5c94_88cd,
+            0x0580_1566_eb9e_119e,
+        ]),
+        Fp::from_raw([
+            0x0856_6055_b887_ee01,
+            0xb109_a509_44ee_f543,
+            0x5ae4_5893_70df_d5ee,
+            0x0263_bdb8_654c_f124,
+        ]),
+        Fp::from_raw([
+            0x6919_e2fe_d09a_106d,
+            0x44f3_bba5_15ec_df02,
+            0xf808_ad8d_077b_7f0b,
+            0x36b5_4477_8b2f_db94,
+        ]),
+        Fp::from_raw([
+            0xe67c_1a55_ef82_294c,
+            0x9b95_f409_abdf_0504,
+            0x0151_d4bf_703c_3834,
+            0x3fb1_f7ae_c47c_be99,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc194_ab7b_e2a1_1e6d,
+            0x2feb_e613_1359_e156,
+            0x4fbe_c3fc_0dce_ee44,
+            0x304b_46f5_2d59_7b96,
+        ]),
+        Fp::from_raw([
+            0x7a6d_974d_6b11_1de6,
+            0x998e_3d85_6726_2367,
+            0xd9f6_a0fe_c505_b7f4,
+            0x067d_8595_6dcf_ff7f,
+        ]),
+        Fp::from_raw([
+            0xd506_5b30_fac5_74e3,
+            0x81fb_1a16_a21b_36cf,
+            0x5fd1_4578_c617_2850,
+            0x11fb_2d16_0e41_a184,
+        ]),
+        Fp::from_raw([
+            0x10e0_8610_7399_989b,
+            0x4263_82a5_b966_2d04,
+            0x7a64_f68f_0a74_92d2,
+            0x1ba5_502c_ee2e_a2d0,
+        ]),
+        Fp::from_raw([
+            0xb637_5e27_bd06_9322,
+            0x0153_84a2_a24f_d322,
+            0xb291_c40f_810c_c912,
+            0x3f65_c2df_a6bb_39c1,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x90b8_847a_93f9_7af1,
+            0x73f7_2882_2637_fb48,
+            0xd178_6edf_e404_1197,
+            0x371b_958b_5c79_c889,
+        ]),
+        Fp::from_raw([
+            0xffae_67bd_ba99_c09e,
+            0x3428_1b5b_6f85_7fa6,
+            0xaa19_e3db_492c_3065,
+            0x3c43_0499_4ef6_64d6,
+        ]),
+        Fp::from_raw([
+            0xa4e6_b0b9_d0ab_d85a,
+            0xd341_a27e_d2fd_7eca,
+            0x4f7b_be6b_a029_93ac,
+            0x0d00_3bd3_068f_a94c,
+        ]),
+        Fp::from_raw([
+            0x89b0_56c2_a640_da01,
+            0x7cab_e9d6_677d_b472,
+            0x88ed_4cdf_78e9_6b29,
+            0x1073_cb8c_0851_0e7d,
+        ]),
+        Fp::from_raw([
+            0x8d16_235b_b41d_707f,
+            0xfa79_04e0_ce48_0972,
+            0x4958_f475_2d0c_091f,
+            0x2a5b_ec9b_422b_4dc6,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x8fae_194c_da2e_56c7,
+            0x86c1_fd86_8683_bdbe,
+            0x8f72_b60f_a985_f7aa,
+            0x379c_4a9b_4174_c587,
+        ]),
+        Fp::from_raw([
+            0xc4fa_1a05_ddb5_8c82,
+            0x51ac_7d21_2c5a_5c6d,
+            0xdb91_1d57_b338_e78f,
+            0x3634_e042_e79d_046a,
+        ]),
+        Fp::from_raw([
+            0xfac4_68af_2584_3a01,
+            0x5923_d474_ce54_97a3,
+            0x1484_d1a6_d429_93ac,
+            0x3ace_9763_10c5_040e,
+        ]),
+        Fp::from_raw([
+            0x30ff_7d04_f243_e6f5,
+            0x55a9_306e_b688_94d6,
+            0x4bc2_e6e4_c610_b9df,
+            0x3f5a_856a_b863_b758,
+        ]),
+        Fp::from_raw([
+            0xa3d5_5585_94b3_149b,
+            0x83de_b87c_8950_4544,
+            0x5dab_0b1f_8d04_eae1,
+            0x0d52_822f_5581_fe9c,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x625e_5fff_a669_6596,
+            0x4c8f_c44f_7092_33f7,
+            0x59bb_09d2_af4f_c104,
+            0x3c11_9e17_3586_c220,
+        ]),
+        Fp::from_raw([
+            0xfe28_7859_994c_96b5,
+            0x560f_1383_92a8_d4a5,
+            0x584f_af8c_089d_82fd,
+            0x3e15_4fd5_a026_d7c6,
+        ]),
+        Fp::from_raw([
+            0xa5ff_9f73_fe74_de50,
+            0x495a_c3b4_9553_b2e1,
+            0x3e0c_ac53_fcaf_7e84,
+            0x39b1_6b0f_c370_0aa9,
+        ]),
+        Fp::from_raw([
+            0xd80b_0824_2926_03bc,
+            0x9b5f_aa7c_0bfe_94c8,
+            0x77b9_1a4f_6d3d_caef,
+            0x2b71_5e21_640c_fb6f,
+        ]),
+        Fp::from_raw([
+            0xc60f_eb5e_243d_32a0,
+            0xe1ed_8197_1f40_772e,
+            0x8d64_8691_5f66_23f4,
+            0x306b_ef0c_637b_5d7c,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x44b3_13e8_118e_7d0c,
+            0x2827_91ef_6284_b73a,
+            0x0c8c_89cb_df4f_b1bd,
+            0x0d76_0a21_32c9_092b,
+        ]),
+        Fp::from_raw([
+            0x5587_3ec4_db82_174e,
+            0xbc7b_18e8_2b35_aa47,
+            0xd843_9f60_a66a_a61f,
+            0x2468_6694_8161_0965,
+        ]),
+        Fp::from_raw([
+            0x7364_ad9a_2f69_8bd4,
+            0xa22b_172b_f7af_e083,
+            0x1c71_9c2a_fab1_272e,
+            0x23b6_ea9e_4d1f_de70,
+        ]),
+        Fp::from_raw([
+            0x7db5_6225_b3a9_459b,
+            0xeaa4_608a_354e_d323,
+            0x33d2_11d4_5a55_5b5c,
+            0x1f64_c178_25c1_ce93,
+        ]),
+        Fp::from_raw([
+            0x693c_88b1_3ae2_3039,
+            0xbc2c_45fd_223c_03d0,
+            0x5d29_dde5_0cd6_06a1,
+            0x0b66_fa87_587a_b95d,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2cc1_84d5_dd8e_f0cf,
+            0x6c17_fe64_e76f_8590,
+            0x33e5_4e5e_17f6_5cb2,
+            0x3086_c386_0266_98e7,
+        ]),
+        Fp::from_raw([
+            0xb545_e405_c7fc_b94e,
+            0x1555_4f61_0078_4bb2,
+            0x1362_4d58_b787_5b17,
+            0x1d77_0c0c_c2c2_2312,
+        ]),
+        Fp::from_raw([
+            0xd85b_c42c_3658_d9ca,
+            0x40db_e38d_5e8c_f1dd,
+            0x8c48_0fc6_a55b_1e56,
+            0x2ea5_c983_7af4_4598,
+        ]),
+        Fp::from_raw([
+            0xf7b5_775c_1210_3a22,
+            0x9dce_4366_b88d_7be5,
+            0xee1e_6699_1ccf_b249,
+            0x35b7_5e89_e794_282c,
+        ]),
+        Fp::from_raw([
+            0xb2fe_f4a7_bfc9_adf2,
+            0x7165_7cd6_69f7_b377,
+            0x12d5_831a_aa6b_2b30,
+            0x3fc5_c930_31cb_cecf,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb400_b529_c545_f5b4,
+            0x09e4_1c7b_50f9_aa95,
+            0x98cd_4b49_104e_56ea,
+            0x3789_5bdf_e29a_174b,
+        ]),
+        Fp::from_raw([
+            0x7a1c_ca0e_96cf_0682,
+            0x1b84_82ac_5feb_bd3d,
+            0x68d8_cc71_40c2_55ed,
+            0x1049_85a4_8aa7_e0a6,
+        ]),
+        Fp::from_raw([
+            0x9060_1a86_a4a9_17a4,
+            0x1a79_ca83_e68e_9ef5,
+            0x4e7d_94d4_0fb1_043a,
+            0x1182_20b3_0330_f195,
+        ]),
+        Fp::from_raw([
+            0xaf0f_f7a0_d5ee_9991,
+            0x94a9_528d_34cb_355f,
+            0x3c13_d211_efac_076b,
+            0x098b_3be7_845a_6354,
+        ]),
+        Fp::from_raw([
+            0xb758_0f0c_0555_878f,
+            0xde0e_52d1_936a_9a10,
+            0x7e02_c5c5_24a8_cc56,
+            0x1903_5c69_cbaf_0e0e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xad04_30af_cfbc_5669,
+            0x3078_0055_829c_1fae,
+            0xd01e_8c48_d49b_b257,
+            0x2b8f_dad2_064a_6f58,
+        ]),
+        Fp::from_raw([
+            0x179c_0f3e_24d0_f718,
+            0xa0c5_6631_4909_3c8a,
+            0x2b49_4bca_3b03_0f56,
+            0x3aaf_796b_7104_1e8b,
+        ]),
+        Fp::from_raw([
+            0x40d6_1ef8_d09c_5296,
+            0xbc66_9aaa_70f0_9ba0,
+            0xf538_2df3_636f_4d60,
+            0x101c_d658_65ab_c573,
+        ]),
+        Fp::from_raw([
+            0x8019_9731_d44a_4b25,
+            0x1ed7_d34b_9a1c_f635,
+            0x438b_fe00_6208_2d4e,
+            0x2581_f83d_616d_932b,
+        ]),
+        Fp::from_raw([
+            0x36fc_6d45_1a03_5916,
+            0x317a_189d_c4f6_fc5c,
+            0x9f51_f176_ed42_d008,
+            0x0d5c_b6e1_9c9a_ac7d,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x41ce_35f9_25c9_941e,
+            0x2d1d_e6da_bd3c_1753,
+            0x2db9_fd48_7f72_076f,
+            0x0e36_7d17_4235_01e6,
+        ]),
+        Fp::from_raw([
+            0xa8d2_f94c_e4ad_c890,
+            0xa31e_c714_0e31_7909,
+            0xebe6_b81d_adf0_ff5f,
+            0x3f3f_101f_7c8a_bd6b,
+        ]),
+        Fp::from_raw([
+            0xd32c_6ef8_9ce7_04e9,
+            0xcaef_5ae6_29e1_265c,
+            0xd903_4ca8_9a65_8817,
+            0x1eb3_b147_885e_1261,
+        ]),
+        Fp::from_raw([
+            0xf570_2212_226d_7140,
+            0x9221_40e4_1b93_c5e7,
+            0xd32b_01c3_fbc2_94b2,
+            0x1595_d95d_ac2c_4653,
+        ]),
+        Fp::from_raw([
+            0xf2e9_7ccc_73de_80ae,
+            0xabe6_ed5b_fc19_9c73,
+            0x21be_9f08_df33_6d3b,
+            0x0b9d_f0b8_1af0_72be,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa957_4601_e88b_6559,
+            0x5c4f_54c5_92ec_2c69,
+            0x954e_9038_6d40_cc7d,
+            0x2a1a_8c6d_54ab_da22,
+        ]),
+        Fp::from_raw([
+            0x3408_102c_0d1e_6363,
+            0xf24e_6d47_ad1a_4baa,
+            0xc2fa_04ca_4452_36fa,
+            0x1ade_9e2b_734e_937f,
+        ]),
+        Fp::from_raw([
+            0xe494_0862_3233_6808,
+            0x472a_bc02_9080_84f2,
+            0x711f_d813_9418_509e,
+            0x008a_5162_adf5_ebd8,
+        ]),
+        Fp::from_raw([
+            0x1009_6222_bc83_cc51,
+            0x57a2_1fbe_e9be_8c4a,
+            0x9652_46bb_7bb8_c04b,
+            0x324c_64ef_2693_e966,
+        ]),
+        Fp::from_raw([
+            0xa492_f8cb_7151_4219,
+            0x25b0_ce0f_3b9a_7431,
+            0x0fbf_e7ef_dcfa_9065,
+            0x3f14_138e_ee87_c93b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xae8f_774b_c870_3009,
+            0x8405_74f6_78b2_534c,
+            0x5574_15ad_181f_1399,
+            0x0db9_9fa5_ce25_d50f,
+        ]),
+        Fp::from_raw([
+            0x095b_dbe7_33e9_7381,
+            0x3299_6772_3ec0_29da,
+            0x5211_041a_4bde_9d79,
+            0x23d9_8470_2589_f327,
+        ]),
+        Fp::from_raw([
+            0x84c2_2d9f_9d28_4726,
+            0xa37d_1b94_e756_8699,
+            0x8635_77f1_0e29_dc66,
+            0x1566_21c4_691a_9240,
+        ]),
+        Fp::from_raw([
+            0xb83a_8941_3d07_8e4b,
+            0xd356_e41e_0951_d38f,
+            0x0adf_fe34_f6aa_8201,
+            0x1b1e_774a_7ec9_0365,
+        ]),
+        Fp::from_raw([
+            0x120f_eab6_6539_1ea9,
+            0x76a1_ebd9_0781_639e,
+            0x5d96_6c1d_7827_eee0,
+            0x1635_0c68_98d0_4d35,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa68e_3f64_24dd_2dfb,
+            0xb98f_4858_c345_e8e0,
+            0x6c1a_7b8f_1c5a_22c3,
+            0x2603_e0fd_0326_4a85,
+        ]),
+        Fp::from_raw([
+            0xbb8e_0a89_e50b_42e2,
+            0x3403_1f8a_31b4_790e,
+            0xe4f1_520b_e70f_5b01,
+            0x100d_2213_42e6_4ed7,
+        ]),
+        Fp::from_raw([
+            0x79d9_6186_747a_4606,
+            0xcee0_dd3a_c5a7_bcaa,
+            0x8ecc_028b_5508_5ec2,
+            0x0e61_bad8_5ce9_0943,
+        ]),
+        Fp::from_raw([
+            0x1d16_4d1f_c7b8_be90,
+            0x2f24_fab7_641b_095f,
+            0x20ea_761d_8f2c_5bb4,
+            0x1a64_e108_621e_1340,
+        ]),
+        Fp::from_raw([
+            0x9dd5_f61d_f3a0_85d7,
+            0xa465_26c9_d785_03dc,
+            0x97ff_d761_e9ae_8b0f,
+            0x097f_0f28_fd29_9e35,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xfe17_9cd6_dce2_7505,
+            0x9c96_ff71_7c7d_0c7c,
+            0x6aca_5e5e_39be_9df6,
+            0x1d10_63cb_1be0_f9f9,
+        ]),
+        Fp::from_raw([
+            0x2380_3733_e13a_d863,
+            0xd32b_1cf6_7f76_ba92,
+            0xb8f3_ba08_cba2_7cae,
+            0x3e30_f5d4_8b3c_2475,
+        ]),
+        Fp::from_raw([
+            0xcf55_86af_5ce3_824c,
+            0x4761_13cc_791e_e110,
+            0x0601_7fa2_6430_d204,
+            0x2b30_db41_98cd_8325,
+        ]),
+        Fp::from_raw([
+            0x9b15_3264_7fca_7bfd,
+            0x2dd0_1abf_aea3_71de,
+            0x3c02_2ec5_1dcf_8d97,
+            0x2b52_0e37_4519_be20,
+        ]),
+        Fp::from_raw([
+            0xfbc5_602b_ea46_cb37,
+            0xd0ef_5619_1490_3229,
+            0x822f_8a97_a8d2_f127,
+            0x183b_9a8e_45fd_480e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x98ad_be84_c143_0979,
+            0x7cf8_4fc0_082b_8dc0,
+            0xd9a9_4c16_28a5_7c81,
+            0x327f_5e14_1e47_58d3,
+        ]),
+        Fp::from_raw([
+            0xde07_0ddc_117b_ac71,
+            0xa714_ea56_17b9_4de6,
+            0x167d_e139_1390_1831,
+            0x3d0e_1203_6899_e5be,
+        ]),
+        Fp::from_raw([
+            0x0805_7f8f_e49c_df0b,
+            0xdf1a_d468_1e3a_c04d,
+            0x080d_0aec_4b81_dd5c,
+            0x1d94_66d5_0efd_1be3,
+        ]),
+        Fp::from_raw([
+            0xed65_92e1_463f_3d00,
+            0xaff1_d242_0b8f_6a7f,
+            0xda3f_495a_9a4f_0f2a,
+            0x2360_abd7_728d_a2dc,
+        ]),
+        Fp::from_raw([
+            0xeaf6_4ed6_7a30_736c,
+            0x78ad_fd4f_af2f_ae7b,
+            0xa1a2_837e_5222_1502,
+            0x23c1_df4d_dd6d_a863,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1342_bc04_3764_cf77,
+            0x6107_f330_6266_1e57,
+            0x7382_73f9_4516_a9d5,
+            0x1e98_ec3b_325a_2a11,
+        ]),
+        Fp::from_raw([
+            0x5f93_af1b_3aee_b582,
+            0x7aeb_41eb_b3a9_140f,
+            0xf511_dc9a_9bc5_7c1e,
+            0x1341_fa99_aca4_bfc0,
+        ]),
+        Fp::from_raw([
+            0x2f80_ba38_cb79_2dc5,
+            0x70b6_c6fc_4685_a548,
+            0xe8e1_96d9_e363_0400,
+            0x28d6_207e_409a_b1c6,
+        ]),
+        Fp::from_raw([
+            0xc1b6_a847_9f75_eaa7,
+            0x081d_88c1_35a5_56db,
+            0x048d_64a2_2ad1_b75b,
+            0x3397_3520_a1d9_fb67,
+        ]),
+        Fp::from_raw([
+            0x5c3a_9205_d01b_85d8,
+            0xfa85_ce48_125e_f727,
+            0x78fd_253d_0b52_75ec,
+            0x3bcb_7630_fc45_d34b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x5472_63a4_86b4_2e34,
+            0xe4ce_ac4b_993b_5342,
+            0x2d73_bb93_33e9_b854,
+            0x1287_f419_048e_8132,
+        ]),
+        Fp::from_raw([
+            0xcc2c_7e75_d06f_c277,
+            0x1d37_2566_1872_2f08,
+            0xef46_d669_e449_ccdc,
+            0x2a2f_5a5a_6894_71d5,
+        ]),
+        Fp::from_raw([
+            0xc2ac_f3b0_fa28_bc92,
+            0x2997_6d44_2e34_9bc1,
+            0x7e35_4947_f2b6_4490,
+            0x38c9_13fd_c729_a28b,
+        ]),
+        Fp::from_raw([
+            0xbc69_556f_f3bf_6b09,
+            0x499d_532c_5f90_bf7f,
+            0x24b6_f7ed_47cd_6581,
+            0x30ac_2452_c3a0_7bb9,
+        ]),
+        Fp::from_raw([
+            0x687b_c734_eada_a2d4,
+            0xc261_e587_0610_557f,
+            0x34f9_2b17_f4f3_bd8e,
+            0x3f3b_a6f9_f12c_a6f9,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x30e2_e316_c89e_e635,
+            0xd448_3cd3_19d8_2155,
+            0xed85_9f57_87fe_20b7,
+            0x11d9_eedd_a8d9_4fcb,
+        ]),
+        Fp::from_raw([
+            0xfca3_3555_2c45_81d1,
+            0xfcd1_6369_9b5b_4fda,
+            0x2c89_feb5_9849_d014,
+            0x2998_1cff_92be_6c88,
+        ]),
+        Fp::from_raw([
+            0x8009_7c4e_4437_3dc8,
+            0x4d34_9f42_b52c_3e00,
+            0xdeb5_7f7f_82dc_511e,
+            0x3086_af93_1c41_d791,
+        ]),
+        Fp::from_raw([
+            0xf5fb_bb3a_9fd8_9fbd,
+            0xf512_203c_ab70_6428,
+            0x392a_39cc_10a6_7112,
+            0x1555_16da_7a22_9b61,
+        ]),
+        Fp::from_raw([
+            0xa792_9b12_d3e8_8519,
+            0x02c3_614a_b909_ae2f,
+            0xd0d8_86a0_2167_cf85,
+            0x0134_4d21_e02b_9c20,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xbf61_1bd0_0291_8820,
+            0x6464_1627_192e_3acd,
+            0x5209_8066_bd36_9332,
+            0x0a77_e0a8_5b06_c1b1,
+        ]),
+        Fp::from_raw([
+            0x3a86_384e_274c_8c94,
+            0xb509_d19e_97ae_1bac,
+            0x72f6_79bf_fec0_121f,
+            0x3efb_107e_bed9_b446,
+        ]),
+        Fp::from_raw([
+            0x7af2_1b05_f565_4482,
+            0x6ec4_849a_0c78_3a3b,
+            0xe03c_011d_b9aa_b4f8,
+            0x3c0c_4b44_1b0e_a7ff,
+        ]),
+        Fp::from_raw([
+            0x70c2_b0ac_02dc_dafe,
+            0x5cd0_8337_4fbe_c263,
+            0x97e4_341c_d188_09ef,
+            0x2807_2c7b_fa64_f6cb,
+        ]),
+        Fp::from_raw([
+            0x7727_d071_663a_b22b,
+            0xa3a1_5ec6_d8b0_786d,
+            0x5b2f_7435_ed8f_67dd,
+            0x1962_306e_92b3_c729,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xd64f_9d5b_94ea_d6e7,
+            0xe64e_3a0a_1dcc_db25,
+            0x2294_9bc1_6c23_08ad,
+            0x3e28_7fec_491c_6862,
+        ]),
+        Fp::from_raw([
+            0x33f9_c5f3_4d76_5d42,
+            0x3907_4b35_594e_0bd1,
+            0x8a7f_90e6_01f2_a9ab,
+            0x2a95_d47f_b725_b397,
+        ]),
+        Fp::from_raw([
+            0x3b96_36f9_653f_f58c,
+            0xbc76_c76c_c7ea_306b,
+            0x0a4d_826e_4abf_3874,
+            0x29c6_03ec_c031_a975,
+        ]),
+        Fp::from_raw([
+            0x1215_b9a6_d68d_e07c,
+            0x0941_227a_bc3a_2a5b,
+            0x0117_2bb8_2a28_38e5,
+            0x0bbf_f6ba_283a_a42f,
+        ]),
+        Fp::from_raw([
+            0xcc88_c693_e0d3_8175,
+            0x1689_5d88_e6de_3336,
+            0xac84_2632_2e88_ff6f,
+            0x3ff4_1b13_d4cb_3140,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xebfb_3ffc_7652_ab24,
+            0x668f_e9b1_823f_e90f,
+            0x3362_912a_460b_e95b,
+            0x0304_3688_d4c9_9176,
+        ]),
+        Fp::from_raw([
+            0x7d22_54c5_b0e2_8f73,
+            0xaf8e_c7f0_d78b_357e,
+            0x36a6_7da2_c691_ff3e,
+            0x33a2_9a0d_56a7_a64d,
+        ]),
+        Fp::from_raw([
+            0xb333_c0c6_211a_c882,
+            0xb3e9_712f_e4c8_8f98,
+            0xa271_0ad5_e911_4486,
+            0x185d_b562_fc75_b43b,
+        ]),
+        Fp::from_raw([
+            0x1db3_f28f_5163_b1fb,
+            0x75d5_23f9_3271_1c6e,
+            0x8952_b3ef_292c_683f,
+            0x147b_89a0_cff9_083b,
+        ]),
+        Fp::from_raw([
+            0xabaa_52c7_b424_f5a9,
+            0xef92_021c_6554_9b2d,
+            0xbadf_1f3e_d677_e50c,
+            0x1318_7913_6781_5809,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x8a89_62ca_f0b6_84c0,
+            0x9437_5ff2_559f_bc58,
+            0x4ed2_1f2e_2b16_af21,
+            0x026b_ebcc_38f0_b280,
+        ]),
+        Fp::from_raw([
+            0x9dcc_b697_92de_8bd2,
+            0xcb5c_d537_8f7a_6f36,
+            0x4ed2_bdb9_a86e_b955,
+            0x03eb_af5f_0602_347c,
+        ]),
+        Fp::from_raw([
+            0x8b45_b548_bd3b_3655,
+            0x553a_0ed4_cada_2db0,
+            0xb32d_3a42_eed0_3f7a,
+            0x3626_d91f_9f05_334c,
+        ]),
+        Fp::from_raw([
+            0x0102_0c95_01f1_10f1,
+            0xb47b_b051_e05e_f063,
+            0xe509_641d_bc67_e55d,
+            0x2636_ac2a_c559_be8f,
+        ]),
+        Fp::from_raw([
+            0x3f2f_f887_3795_ca86,
+            0xfbc8_cc9c_902c_1692,
+            0x9725_f35c_d8da_f52f,
+            0x1978_52b9_a62e_1677,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7b6e_452b_4ede_9a35,
+            0x2444_4904_8f8f_c84f,
+            0xb280_dba7_744c_f67e,
+            0x1c3e_49f3_3fd7_3480,
+        ]),
+        Fp::from_raw([
+            0x60e8_ef4f_5735_21b8,
+            0xbe3d_0371_bd97_a9c0,
+            0x7944_d7aa_48c2_7648,
+            0x0c3a_7aae_b5f6_5d90,
+        ]),
+        Fp::from_raw([
+            0xa63d_5c92_4e67_f677,
+            0x7c30_4201_5e93_8f97,
+            0x2b09_92ee_517b_6970,
+            0x1106_537b_f315_0b44,
+        ]),
+        Fp::from_raw([
+            0xc680_66ba_c8f2_c467,
+            0x21dd_6ae5_004c_4deb,
+            0x935b_5a34_7f16_7ce7,
+            0x1023_721f_d728_5260,
+        ]),
+        Fp::from_raw([
+            0xfbf7_15f3_d58f_e3f2,
+            0x0a89_dcda_8c84_bf68,
+            0x5456_c74b_6518_6c86,
+            0x2d52_fbc9_5404_515f,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa1ea_d082_41a8_96c4,
+            0xe1d3_9643_c189_f3ca,
+            0xbc66_00f7_dc2a_72c8,
+            0x1f82_afe8_eb16_611a,
+        ]),
+        Fp::from_raw([
+            0xfa6f_3dbc_a0f9_54bd,
+            0xac46_883f_e1cf_eb8c,
+            0x8a78_9e0e_488c_6fb2,
+            0x3bb8_684c_f815_ae6d,
+        ]),
+        Fp::from_raw([
+            0xfe9e_1bb8_bda9_5d1d,
+            0x8f5a_8fc4_3653_5766,
+            0xc431_b098_cdb3_c451,
+            0x3d5a_1a6e_5713_06fa,
+        ]),
+        Fp::from_raw([
+            0x30ca_ee4d_fe50_55f1,
+            0xe28e_2f07_ce8d_9320,
+            0xe74c_5c99_b20c_3314,
+            0x2c3b_1b86_ce90_cb3f,
+        ]),
+        Fp::from_raw([
+            0x4786_8d0e_9834_f754,
+            0x97ff_f85e_c102_88b8,
+            0xbce7_30d8_af86_fe03,
+            0x0bfb_a44d_41c4_9044,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc413_eaf9_1d82_e0c2,
+            0x4a4b_2a79_045c_ee61,
+            0x69c6_15cc_8d7f_36fe,
+            0x0b79_924b_9e44_6623,
+        ]),
+        Fp::from_raw([
+            0x7079_63ee_6f9f_74ef,
+            0x4b13_f6a4_ca24_e7b9,
+            0x7022_3a40_cc0d_b910,
+            0x048a_11ec_75eb_154b,
+        ]),
+        Fp::from_raw([
+            0xb16f_9e93_0cc3_5ebc,
+            0xdec5_a063_4569_21b6,
+            0x6b61_6e72_539d_5751,
+            0x1128_44b7_c50e_7e67,
+        ]),
+        Fp::from_raw([
+            0x9bc7_1867_e1c0_6471,
+            0xf8d9_0bc9_a7f3_ce7c,
+            0x7af8_ceef_5008_d1ed,
+            0x217b_616b_50e7_2954,
+        ]),
+        Fp::from_raw([
+            0x717c_6391_7dcb_7b79,
+            0xa790_b35a_8503_da46,
+            0xbccb_b46d_cd25_22de,
+            0x3f9a_0b84_02ff_a291,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa2eb_803e_313e_96cc,
+            0x4447_9956_f533_7b12,
+            0x9ebe_febf_059e_b90f,
+            0x2942_2397_2f4c_7e9c,
+        ]),
+        Fp::from_raw([
+            0x235f_3c6c_3078_07cd,
+            0x4e32_c557_3ec9_1acf,
+            0x8e0d_a895_3671_76d2,
+            0x207c_7c32_631a_75fe,
+        ]),
+        Fp::from_raw([
+            0x43d1_4764_5c39_44ca,
+            0x7d38_cb4a_0e12_a15d,
+            0xd357_5eb2_380b_466f,
+            0x20f9_5577_3b13_b160,
+        ]),
+        Fp::from_raw([
+            0x4e5c_836a_50c3_3e9d,
+            0xa9bd_6b90_4853_d5ef,
+            0x7a44_16ec_6b36_5d51,
+            0x1ccc_c7dd_9163_9253,
+        ]),
+        Fp::from_raw([
+            0xd7be_4e02_a2a7_796e,
+            0xe4d6_a895_414e_3883,
+            0x09dd_66bc_878e_f46f,
+            0x0de8_7e0a_4652_468d,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4168_d33e_0fba_662a,
+            0xa3e1_f432_d0f2_b1cf,
+            0x7324_28df_f7c9_08ce,
+            0x241f_c892_0bb3_73ab,
+        ]),
+        Fp::from_raw([
+            0x78ab_bcf9_65f3_96b0,
+            0x9fcf_b884_96f8_8994,
+            0xbffc_c29e_afa1_38f8,
+            0x1dcf_981a_e333_b921,
+        ]),
+        Fp::from_raw([
+            0x5fab_2155_fcce_45f5,
+            0x12c5_5627_2e3f_04d2,
+            0xd8e4_b124_30dd_0e4e,
+            0x0469_6dd5_b4f4_95e7,
+        ]),
+        Fp::from_raw([
+            0xa974_a45b_4222_b38f,
+            0x4953_73d0_1113_0fa8,
+            0xe24f_d3f8_5509_179c,
+            0x2e33_4f20_a310_8165,
+        ]),
+        Fp::from_raw([
+            0x107b_76a9_a305_55c5,
+            0x02d6_d758_f2f1_fdc0,
+            0x6a18_6f14_c26f_44b1,
+            0x1fb8_9f92_8883_afce,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x027c_2222_8639_d8a9,
+            0xd539_2063_d269_672f,
+            0x75a2_2bc9_0539_bb51,
+            0x0d32_1562_e841_c4f5,
+        ]),
+        Fp::from_raw([
+            0xdc5b_7c68_35de_e517,
+            0x388d_87d8_ab2d_6680,
+            0xb174_2abb_2587_ae29,
+            0x26c3_2362_7ed0_150a,
+        ]),
+        Fp::from_raw([
+            0x36a7_6d69_60b1_5189,
+            0x6aa3_af04_1f3e_cb04,
+            0xcd9c_8fb2_ab71_88a7,
+            0x0ae2_89ea_594b_034d,
+        ]),
+        Fp::from_raw([
+            0xd0ba_2aef_1328_497c,
+            0x3ae1_98f6_fccb_58e5,
+            0x6210_9b7b_30dd_b872,
+            0x0f7a_97ff_77b0_be2e,
+        ]),
+        Fp::from_raw([
+            0x41ee_7e33_26d8_eef7,
+            0x8d42_e745_e9e1_48ae,
+            0x73a8_bddf_5df5_932d,
+            0x3549_baef_c03d_8180,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x7323_ca6b_25f6_56a6,
+            0x8177_8701_0085_fd57,
+            0x85cf_19fc_e812_0044,
+            0x28ef_c9f6_1c3f_168e,
+        ]),
+        Fp::from_raw([
+            0x1b8d_9696_b8ae_8f8f,
+            0xbb1e_91bf_43e5_f12b,
+            0x0d2f_8b7c_415c_9563,
+            0x0382_d8f5_181d_c84d,
+        ]),
+        Fp::from_raw([
+            0x67b8_f7ac_313a_d5e8,
+            0xdb06_ab8c_4039_5e2e,
+            0x1906_c0bd_7740_4cbb,
+            0x0d58_dd07_084c_c5b7,
+        ]),
+        Fp::from_raw([
+            0x8b0a_3718_17a1_e0af,
+            0x188f_dab8_2975_da33,
+            0xfadd_d272_cb9e_93c8,
+            0x0b92_1a47_48ad_df10,
+        ]),
+        Fp::from_raw([
+            0x030f_f2f8_8a16_80cc,
+            0x8422_0604_4636_e2e0,
+            0x9803_0d64_6652_97d7,
+            0x21eb_5501_b745_f081,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6d51_09e6_81b2_d283,
+            0x6c08_29d4_a128_51b2,
+            0x53ce_e32b_81df_abea,
+            0x2cd9_1c3b_2c13_3579,
+        ]),
+        Fp::from_raw([
+            0xabde_25c0_eceb_ab53,
+            0x7aec_0752_c61f_9724,
+            0x924c_bf4c_1c37_f6f0,
+            0x133d_8f85_23a0_739a,
+        ]),
+        Fp::from_raw([
+            0xb5e8_4b46_ad91_cfbf,
+            0xc0e3_1f47_f033_b0f8,
+            0xcb13_2bef_ef32_fe79,
+            0x2fbf_a082_9d39_1421,
+        ]),
+        Fp::from_raw([
+            0x2d88_24c3_f1b8_bc66,
+            0x9527_d644_8030_db0c,
+            0xfa3c_00bf_583d_23e0,
+            0x3fb1_f1bd_519d_d71b,
+        ]),
+        Fp::from_raw([
+            0xbcf2_68e4_89e9_df68,
+            0xf056_214f_a53a_7d5a,
+            0x0bde_70cf_1b59_33b1,
+            0x1ecc_c9f5_ce2d_738e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xc96c_e6a3_60f5_c1e1,
+            0x4887_af79_3b0d_6acd,
+            0x7d76_4c00_65d6_c019,
+            0x21c4_d2f4_998c_09b2,
+        ]),
+        Fp::from_raw([
+            0x4430_6b39_180d_9734,
+            0xb3ff_f6e7_24f4_4c30,
+            0xba9d_f3f3_f4bd_0d94,
+            0x0f2e_64b5_b230_a565,
+        ]),
+        Fp::from_raw([
+            0x4ac2_6a1b_c92e_0089,
+            0x8ae0_34f6_7ba3_3280,
+            0x8ba6_ecac_4871_37f1,
+            0x26f6_4255_4137_7688,
+        ]),
+        Fp::from_raw([
+            0xfec2_27dd_bc3f_0d24,
+            0xdaf7_0e42_5654_3c6b,
+            0xdaa0_3c1a_5830_9ce6,
+            0x10c4_8c70_c147_55f3,
+        ]),
+        Fp::from_raw([
+            0x10d2_f511_998a_ffc8,
+            0x4c94_e097_7ac6_b519,
+            0xe010_1bca_f5dc_3387,
+            0x0e3e_5513_225b_0e99,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe571_9904_ca3f_823e,
+            0x0aa1_aebf_ea4d_b001,
+            0xd2bc_9f26_07d6_9dd0,
+            0x3afc_d063_d990_71d8,
+        ]),
+        Fp::from_raw([
+            0x51db_1b04_4b3c_dba0,
+            0x28bb_3e71_8379_fa06,
+            0x93dd_23b7_98d5_2a9a,
+            0x15ab_e264_c4c7_dd40,
+        ]),
+        Fp::from_raw([
+            0xe8c3_6c99_6e36_3671,
+            0xfb1f_ec8b_016a_c3c9,
+            0x7d48_4033_2304_3692,
+            0x2917_8d27_5a24_0fc5,
+        ]),
+        Fp::from_raw([
+            0x58b0_4dde_c6bf_7ad8,
+            0x6b12_8c92_c061_9370,
+            0xbfe7_c014_5603_df1c,
+            0x28bd_87ff_33f3_df67,
+        ]),
+        Fp::from_raw([
+            0xa386_305f_3ee2_6b50,
+            0xd5d0_e116_b8f9_5470,
+            0xae54_90f4_8c56_0af5,
+            0x130d_84d1_d830_618f,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x508b_9e49_da9f_c902,
+            0xad90_404a_7f84_8cce,
+            0xee9c_da63_1392_901a,
+            0x169d_e08f_b181_65b2,
+        ]),
+        Fp::from_raw([
+            0xa892_f256_da55_c7dd,
+            0xcbb9_c097_32e1_dd7c,
+            0xa873_7e9a_1df7_b0d1,
+            0x2fb1_e089_543d_7019,
+        ]),
+        Fp::from_raw([
+            0x9480_35f1_b513_3eb0,
+            0x3e1d_5bf3_e06d_173d,
+            0x8377_7602_ebdd_ec76,
+            0x159b_1d5e_2fa6_db09,
+        ]),
+        Fp::from_raw([
+            0xa290_74a1_85fb_20fa,
+            0x25ad_cd00_3bf4_8e02,
+            0x8683_6121_a748_8dad,
+            0x2327_ff43_4d02_ab35,
+        ]),
+        Fp::from_raw([
+            0x1bf3_1a2c_1767_0ae0,
+            0xf9fc_55ac_58ee_5119,
+            0x7a99_877f_9069_5307,
+            0x2656_37cc_130f_a996,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb15f_bc8a_3c44_9d1d,
+            0xac6e_2b21_8c4e_7377,
+            0x245e_6a61_dd7c_d9f7,
+            0x317c_357a_5078_1402,
+        ]),
+        Fp::from_raw([
+            0x3449_13c4_8202_c581,
+            0x75fa_c13e_f74f_50bf,
+            0x0bbc_2d3a_2b4f_61b5,
+            0x0331_2609_9006_934c,
+        ]),
+        Fp::from_raw([
+            0xa360_14ad_9406_1c87,
+            0x4c93_7d6e_218e_1572,
+            0x7ffa_179d_b2f1_16ac,
+            0x3c92_471a_8991_7007,
+        ]),
+        Fp::from_raw([
+            0x0999_5ac6_56b5_71e5,
+            0x467a_4dee_0cef_46c2,
+            0x9aeb_7d63_b6fa_4a0e,
+            0x2105_bc5c_8485_78ad,
+        ]),
+        Fp::from_raw([
+            0x95d4_fe0b_aecd_94eb,
+            0x4d3a_37e4_5231_7d41,
+            0x7c90_4f92_c94a_64fa,
+            0x1c1f_ea18_2f21_60a2,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9572_3b49_aea4_ff68,
+            0x50c0_90d6_982f_3444,
+            0x6ba0_698c_f58d_9a3e,
+            0x0d9b_7e72_5d17_5b64,
+        ]),
+        Fp::from_raw([
+            0xfe99_b33d_434e_d439,
+            0x66a9_d691_e68e_52db,
+            0x3f18_964d_cc6e_f65d,
+            0x0ebe_d32c_d9ed_5a9a,
+        ]),
+        Fp::from_raw([
+            0x0396_74aa_a1b0_dc79,
+            0x10c2_ca28_2837_7891,
+            0x56b1_cdae_c351_99e0,
+            0x11d5_1d0c_895b_cedc,
+        ]),
+        Fp::from_raw([
+            0xe0cf_891c_e4dc_ae06,
+            0xad0b_588f_e1d2_87c0,
+            0x85b5_d665_975c_741d,
+            0x1b9d_1627_15f0_81e5,
+        ]),
+        Fp::from_raw([
+            0x4e8b_cc92_d655_e5d4,
+            0x0894_456f_1a77_1dcd,
+            0xbb48_273d_5ce9_36d8,
+            0x12d3_bdef_5a0a_1294,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9881_e9b8_a2a6_4110,
+            0x33f1_6527_ba99_682a,
+            0xbfec_49d7_1bc4_96df,
+            0x39f6_1b8d_bdd1_1e67,
+        ]),
+        Fp::from_raw([
+            0xf654_5d42_2f16_5ed9,
+            0xa363_0081_0783_6d2b,
+            0xd950_d377_b140_c7e6,
+            0x3a92_4fd0_7fc3_5796,
+        ]),
+        Fp::from_raw([
+            0xfe8a_ff67_878e_511c,
+            0x9799_a5db_d161_cd6c,
+            0x86b4_eb7a_fdf9_33d6,
+            0x20ed_9722_8d2c_25b4,
+        ]),
+        Fp::from_raw([
+            0x6c15_69bc_9c45_7639,
+            0x03d4_3e8a_5fff_b505,
+            0x9dd8_0c92_dacf_e3aa,
+            0x06b9_0754_cb10_0322,
+        ]),
+        Fp::from_raw([
+            0x38f5_b234_099f_2e5b,
+            0xba8a_0b12_b1ee_99cf,
+            0xbde3_8ab2_a272_0b92,
+            0x37a3_4dac_0103_ee67,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe1ee_c800_1506_ad86,
+            0x9e40_5e43_5836_6f03,
+            0x576a_3ced_c37c_524f,
+            0x2afa_7f34_33a9_ca0f,
+        ]),
+        Fp::from_raw([
+            0x922b_5511_60f1_c8dd,
+            0x9451_c7ce_3c15_e448,
+            0x6bab_c3e7_76bf_46fe,
+            0x04bd_7773_7eed_bf6c,
+        ]),
+        Fp::from_raw([
+            0x24d8_c4e5_1769_b909,
+            0x8945_7374_8e4b_6dc7,
+            0x90c9_82d5_2809_51fe,
+            0x2d43_72bf_9e0a_a8a0,
+        ]),
+        Fp::from_raw([
+            0x86e4_17ae_fd4c_0dcd,
+            0x1895_cec1_3279_7fd0,
+            0x354f_ba0a_0ac5_4ccf,
+            0x2305_b793_bf41_3d84,
+        ]),
+        Fp::from_raw([
+            0xbb46_b941_07a5_7e99,
+            0xca1c_0b62_50ea_7842,
+            0xe005_c564_5eb7_0612,
+            0x0721_5c90_a967_5ed7,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb3a8_ddad_2b3e_d76c,
+            0xb25b_6afe_594b_7181,
+            0x560e_d81c_8894_c6c9,
+            0x1124_c009_d49f_c980,
+        ]),
+        Fp::from_raw([
+            0x8fbb_865f_1702_079e,
+            0x2a91_10b7_51d1_3354,
+            0x1f90_ace1_264d_ae31,
+            0x021b_1dca_f387_56cc,
+        ]),
+        Fp::from_raw([
+            0x6d8b_9e24_90c8_57fc,
+            0x747f_7087_3263_68ab,
+            0xd915_a3e2_afe5_c3e7,
+            0x312f_9804_d40a_9ccb,
+        ]),
+        Fp::from_raw([
+            0xe76c_fb13_e233_cd2a,
+            0xf9fb_5085_2f80_fe88,
+            0x2c61_2cec_24c1_89c3,
+            0x1485_ce84_8a74_1686,
+        ]),
+        Fp::from_raw([
+            0x2612_21ea_b33f_0fc4,
+            0x79fc_2d81_f3a6_ef88,
+            0xde90_af2d_8e41_a335,
+            0x36e0_2a8c_daff_0d2e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1e0c_82a7_fccd_5e2a,
+            0x9936_00ce_b4ae_b2cd,
+            0x498a_62ab_9b02_f251,
+            0x2520_5d62_46ed_8970,
+        ]),
+        Fp::from_raw([
+            0xda81_7ef0_921a_1523,
+            0x3dfb_f4f9_acef_e241,
+            0x3a4f_407e_6170_f20a,
+            0x3154_5c54_2706_ee9b,
+        ]),
+        Fp::from_raw([
+            0x390a_91ac_a9e4_ea9f,
+            0x6acd_e23b_1dbd_2368,
+            0xb99e_e793_3176_80a1,
+            0x3c45_041f_f243_f7f2,
+        ]),
+        Fp::from_raw([
+            0x0427_f46f_c08b_84c1,
+            0xb20e_79a0_dfbc_36bf,
+            0x2f4a_f1c2_1671_9f84,
+            0x063a_6686_9336_311b,
+        ]),
+        Fp::from_raw([
+            0x985f_433a_3f3a_8a56,
+            0x5024_6969_5dce_7f27,
+            0x2322_9c78_bffd_071e,
+            0x2d8c_519e_e26c_59f6,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1922_c1f7_f5cb_e2cc,
+            0x03f3_a770_30fc_e56f,
+            0x03a4_e8de_45ef_d7dc,
+            0x1708_3d65_a4d1_87a3,
+        ]),
+        Fp::from_raw([
+            0x8501_2845_eea5_b2cf,
+            0x3d73_bef9_a854_4e94,
+            0x8f4e_c0e8_59e8_163a,
+            0x33aa_3d33_49af_4f58,
+        ]),
+        Fp::from_raw([
+            0x6c08_a617_8ac4_cf37,
+            0x7412_1b7a_ea60_7468,
+            0x7d4f_2b25_7cb7_8c7a,
+            0x29c9_4614_3bbb_442c,
+        ]),
+        Fp::from_raw([
+            0x9230_f50f_4569_4087,
+            0xd39a_0c53_607d_7331,
+            0x7893_a607_cbc0_00db,
+            0x33e9_19d5_f23e_b802,
+        ]),
+        Fp::from_raw([
+            0xd08e_ceff_6982_52ca,
+            0xadf5_d17d_4439_ba0c,
+            0x571a_c223_9c73_cba4,
+            0x1e93_391d_6d16_f2dc,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xf701_bf66_dda3_23ec,
+            0x074d_afd0_faa1_1908,
+            0x8405_72f2_572f_fed7,
+            0x1e85_23b3_f05d_49f5,
+        ]),
+        Fp::from_raw([
+            0xcf99_dc37_a6d3_28a5,
+            0x18c5_d60f_7227_fc64,
+            0x7a35_9913_c85a_6d2a,
+            0x3971_68eb_1f58_139b,
+        ]),
+        Fp::from_raw([
+            0x6706_332c_e04e_2604,
+            0xb594_66dd_8de5_b894,
+            0x9bcb_8380_5940_8420,
+            0x3059_a69d_7021_acce,
+        ]),
+        Fp::from_raw([
+            0xc77d_b679_de53_2cb6,
+            0x40d8_cd81_f627_3c56,
+            0x51a4_e713_e343_1f89,
+            0x3377_56b6_477b_70f5,
+        ]),
+        Fp::from_raw([
+            0x97d8_c091_e683_76bd,
+            0x463d_355d_4551_ff1d,
+            0xac61_70ef_90ac_3a33,
+            0x3360_c384_ca10_5408,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xbfdb_0202_7a14_07f3,
+            0x6993_092e_1e80_afa1,
+            0xecf0_676e_edee_ef34,
+            0x1664_eeaf_768c_3ac9,
+        ]),
+        Fp::from_raw([
+            0xeb82_6455_f6cd_8a13,
+            0x112b_2b19_d4c1_74be,
+            0xb582_8667_6430_df29,
+            0x0544_b989_24e2_2cd4,
+        ]),
+        Fp::from_raw([
+            0x08e2_a74e_4efa_a87f,
+            0x1f3f_3e5e_dbe8_7746,
+            0x27b0_2ee9_9812_9716,
+            0x1b97_33f8_77e1_4e6e,
+        ]),
+        Fp::from_raw([
+            0x7dbb_d888_074c_ad25,
+            0x6622_d0e3_64d3_ae16,
+            0x8184_e391_5d78_842f,
+            0x1b13_9add_bce0_b2fa,
+        ]),
+        Fp::from_raw([
+            0xc36f_4848_0201_d1f3,
+            0x6592_8ead_6abe_b644,
+            0x908b_66f6_327f_39aa,
+            0x1fdc_7c1a_34a4_1ccf,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x4dbe_fa6f_ffb3_d3d8,
+            0x58ee_f9ef_5d8b_3a0a,
+            0xf2e6_05e1_f025_d299,
+            0x18f5_5744_33c8_82d7,
+        ]),
+        Fp::from_raw([
+            0x4b08_4500_1211_aa14,
+            0xeee9_b310_f2ed_6d87,
+            0xf656_7405_20e7_e61b,
+            0x2c39_9b7d_4948_5085,
+        ]),
+        Fp::from_raw([
+            0xabda_7752_ed0e_044f,
+            0x1578_e818_585b_b129,
+            0x857c_3385_3e04_79bd,
+            0x3513_7757_6f33_5751,
+        ]),
+        Fp::from_raw([
+            0xf9f1_ce29_a039_ca56,
+            0xe46c_bd33_6c93_c82b,
+            0x0e05_3752_02e5_a0ee,
+            0x2c60_d923_ed54_20b1,
+        ]),
+        Fp::from_raw([
+            0x4dfa_1cc7_c813_4210,
+            0xeced_53a1_3606_b587,
+            0xbec0_2e2d_e83b_4c18,
+            0x3e49_8515_ba7a_38c5,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x9cee_e406_bc9a_4fce,
+            0xc134_add9_c47c_2622,
+            0xe34f_19c3_8e63_822a,
+            0x21aa_76dd_cd72_6830,
+        ]),
+        Fp::from_raw([
+            0xbb52_1ef2_8dd0_9284,
+            0x9942_9754_d60a_593e,
+            0xa3bb_b841_4686_0d8b,
+            0x1e78_92de_ecd9_8d9d,
+        ]),
+        Fp::from_raw([
+            0xfc77_40c4_e950_4773,
+            0x60ca_a64c_fd06_247f,
+            0xa1a3_eb81_1934_b981,
+            0x3e87_6a66_0048_7bd5,
+        ]),
+        Fp::from_raw([
+            0xb396_80a3_014f_6590,
+            0x006c_5457_9074_f5a4,
+            0x4909_4ce5_4185_be10,
+            0x231f_8abb_6f9b_25a4,
+        ]),
+        Fp::from_raw([
+            0xbe42_3329_574f_4ad4,
+            0x89f9_4b77_889e_7e62,
+            0x7ef7_fb14_9b05_86be,
+            0x3bfc_dc66_0e36_e7f3,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x96b8_acef_8837_efac,
+            0x84c1_64eb_d4ea_e4d3,
+            0xc8f1_1972_5b3c_bb1e,
+            0x2119_529f_8952_e037,
+        ]),
+        Fp::from_raw([
+            0x0eac_3bae_3569_f1c5,
+            0x1b34_3a76_93cb_2668,
+            0x1b54_4620_6aed_3eb6,
+            0x3e90_7a6a_a9bb_ffb7,
+        ]),
+        Fp::from_raw([
+            0x14ad_6067_127a_a821,
+            0x21ea_e04b_d0ee_de18,
+            0x8442_9207_d4a0_9703,
+            0x065e_b171_06b3_3253,
+        ]),
+        Fp::from_raw([
+            0xa228_c514_c29c_28ef,
+            0x00f7_f403_cfcc_da1b,
+            0x62c4_497d_591e_2c8c,
+            0x2522_0f18_fffa_d8f6,
+        ]),
+        Fp::from_raw([
+            0xfb84_fa07_3dc3_b178,
+            0x1760_1161_a7da_b1ae,
+            0x30b2_9d4a_d3a4_1256,
+            0x00bb_3fba_3007_ce8b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x127e_7dd2_61a5_e412,
+            0xb27b_82fa_5c05_b88e,
+            0x4a9d_a55c_5d9d_4a27,
+            0x3c43_a246_3fc0_eeb6,
+        ]),
+        Fp::from_raw([
+            0x2045_e03f_4b43_9234,
+            0xc846_a281_81ea_891b,
+            0xe784_7bc4_0bb0_2436,
+            0x0c94_c862_2e2a_dcb6,
+        ]),
+        Fp::from_raw([
+            0xe6d2_eb2d_4396_71ea,
+            0x43ed_1bbb_8cbb_490d,
+            0x45f2_9e09_483d_4a0b,
+            0x086f_4f29_102b_507d,
+        ]),
+        Fp::from_raw([
+            0x9288_32aa_6154_68a4,
+            0xf03e_d947_2607_eb23,
+            0x04b8_4da0_be76_18ed,
+            0x1457_4f42_b220_ef6a,
+        ]),
+        Fp::from_raw([
+            0xc24f_7e34_d6ad_ba02,
+            0x6c63_b6d1_718f_a019,
+            0xcacb_48c4_3f43_8f71,
+            0x1364_1c47_28da_f485,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xf5e6_ff35_1dc9_6182,
+            0x6524_c1f0_49f2_c200,
+            0x7fb1_1da2_b1fa_554d,
+            0x2f97_a373_6746_d4ba,
+        ]),
+        Fp::from_raw([
+            0x0f1f_9b9e_11eb_16fb,
+            0x76a1_cb9f_889b_2dc2,
+            0x85a4_f5d5_b598_54a2,
+            0x03bf_687b_5c0b_f012,
+        ]),
+        Fp::from_raw([
+            0x87c7_6bf4_980b_9adb,
+            0xc265_6c6e_c458_1a06,
+            0x742a_a7a3_e11b_a8c3,
+            0x3424_f899_56ae_4499,
+        ]),
+        Fp::from_raw([
+            0x5d41_8235_af34_a6bc,
+            0x6329_d21c_f551_e304,
+            0x01ec_7f46_b8ee_bb86,
+            0x37c1_c901_1409_14df,
+        ]),
+        Fp::from_raw([
+            0x660c_d2be_f0ce_b8aa,
+            0x49da_9dbe_ad39_1930,
+            0x9e31_9095_6be8_e80a,
+            0x1674_e591_ef07_a292,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xfa9c_1833_de0f_ec89,
+            0x0a12_c044_8037_2092,
+            0xfc33_49d4_59e5_c4ba,
+            0x05a8_f8cd_359e_5572,
+        ]),
+        Fp::from_raw([
+            0x0c67_62b1_827e_0726,
+            0x4d68_245e_8396_07b6,
+            0xea41_42c8_f075_eefc,
+            0x0277_7dd1_91c5_bbbf,
+        ]),
+        Fp::from_raw([
+            0xe29d_e1b5_4887_3109,
+            0x4274_d370_3fd1_71f9,
+            0x821f_da8b_7587_aa82,
+            0x050b_6d98_1cc3_fc83,
+        ]),
+        Fp::from_raw([
+            0x79e2_dab6_3205_703c,
+            0xa460_fea8_54fe_91ba,
+            0x9d47_d36b_dfd6_4c08,
+            0x28be_78c3_7aa5_723d,
+        ]),
+        Fp::from_raw([
+            0x6084_15fd_1ac4_be2f,
+            0x5e4c_f10a_44f0_07b3,
+            0x2ccc_bb5c_07d2_f43b,
+            0x005b_b3a6_046e_c3cc,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb1eb_fb5d_ec24_9c4f,
+            0x0138_bece_b7a2_408c,
+            0x074e_e372_02f8_7db3,
+            0x0898_3ba3_ed43_d77c,
+        ]),
+        Fp::from_raw([
+            0xcd46_edbe_add0_ee92,
+            0x67ff_d3fc_f1a7_267a,
+            0x9654_36cb_1b73_fd44,
+            0x1d28_9ecf_00ec_816e,
+        ]),
+        Fp::from_raw([
+            0xff47_d4fa_80a3_41f8,
+            0x50ca_f9f6_7917_4162,
+            0xf1ca_8668_f804_f680,
+            0x2e05_6ce6_3788_b20d,
+        ]),
+        Fp::from_raw([
+            0x87c5_e481_5386_dea6,
+            0x9261_0cd1_55fe_4269,
+            0xe5d4_f390_f938_83bc,
+            0x0b67_14ed_53d7_c0e9,
+        ]),
+        Fp::from_raw([
+            0x8053_5320_e162_2a0d,
+            0x8fbc_655b_fd3a_b539,
+            0x3cb6_251a_4fe0_6069,
+            0x099a_5ff0_28be_2ffe,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x070e_2115_5997_ee34,
+            0x88ce_d104_299b_d299,
+            0xe5fb_bf82_9cca_7bfe,
+            0x0fce_36e3_5b2d_5331,
+        ]),
+        Fp::from_raw([
+            0x8494_b512_e244_9bf0,
+            0x850b_f308_de3f_1815,
+            0x424d_0941_857b_691a,
+            0x1b0c_d5b6_13db_59de,
+        ]),
+        Fp::from_raw([
+            0xc865_52e5_1f63_f6bb,
+            0x8d26_6588_acf2_51e8,
+            0xe7d5_9581_22a1_b6d4,
+            0x0205_d7ed_d37b_dc51,
+        ]),
+        Fp::from_raw([
+            0x7bae_6164_acc8_ec0a,
+            0xe8ee_81b6_29ac_41b0,
+            0xa80d_8104_a396_3e3e,
+            0x12d4_7dfc_8aed_65d8,
+        ]),
+        Fp::from_raw([
+            0xd735_8caa_c0fa_a9de,
+            0xd136_3140_a599_3719,
+            0xb987_7839_fea9_6e1b,
+            0x29a1_9f22_d5fc_13ed,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xab85_d159_09be_8f5c,
+            0x510c_662b_c93f_b350,
+            0x61e6_6110_ca8a_4210,
+            0x2616_9be4_a59a_a5d6,
+        ]),
+        Fp::from_raw([
+            0x4d30_45c4_bdfd_19cf,
+            0x5668_ec89_ffd4_8382,
+            0x0767_3545_5b95_e6f8,
+            0x1c4d_4a53_6c41_36e9,
+        ]),
+        Fp::from_raw([
+            0x165a_477a_369b_8543,
+            0xf12f_ba2b_37dc_11ff,
+            0xba83_c8c7_7e4c_34e5,
+            0x06cb_b56d_f61b_68c1,
+        ]),
+        Fp::from_raw([
+            0x9321_aba4_08aa_4db2,
+            0x7052_d552_9e74_87d6,
+            0xc4da_64f1_70d9_3afb,
+            0x2d8a_e94b_8193_94fd,
+        ]),
+        Fp::from_raw([
+            0x9cbb_914c_9a1d_e0c9,
+            0xdf91_199e_62f2_65d9,
+            0x3cc9_3ea5_7b00_2cd9,
+            0x359e_9373_a38c_1848,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb37d_523d_6e3f_5461,
+            0xa2b9_f9a9_9742_71bd,
+            0x7d26_3e6e_6f24_199c,
+            0x3c80_ebe2_2699_6a56,
+        ]),
+        Fp::from_raw([
+            0x1740_f0d7_ba55_6d83,
+            0x032c_3c0b_34b4_2981,
+            0xa4e2_8f65_5224_1ff9,
+            0x2b71_9963_a1cd_c0ae,
+        ]),
+        Fp::from_raw([
+            0x5ca9_5f5f_897c_958c,
+            0x8648_6449_7432_7061,
+            0xf9fe_eb2b_f8ea_cd67,
+            0x0e23_9ced_1fff_5660,
+        ]),
+        Fp::from_raw([
+            0xddf8_5d7f_975e_405c,
+            0xa711_bf94_e4da_648a,
+            0x4ccb_ab7d_abf2_bbd1,
+            0x00ca_eef5_3535_641d,
+        ]),
+        Fp::from_raw([
+            0x2b11_fd04_c9c0_5416,
+            0xd8c8_d89f_48a3_71f7,
+            0x1efe_5e4f_5087_dd17,
+            0x24d8_688e_f705_b86e,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xb018_2edc_1e5b_86e6,
+            0xe8b4_4278_4886_8fae,
+            0xb8fd_b152_7384_acfa,
+            0x0331_4bbe_c93f_1526,
+        ]),
+        Fp::from_raw([
+            0x3465_f466_a504_95a1,
+            0xe2b7_6017_103e_ec2b,
+            0xb029_0949_9880_c5cf,
+            0x0938_dfe2_d6b3_ccb5,
+        ]),
+        Fp::from_raw([
+            0x39b1_1221_90a1_8e1d,
+            0xebdd_5ad6_2084_9858,
+            0xccb3_8dd3_5ab6_b66a,
+            0x3753_a803_3ee6_6d20,
+        ]),
+        Fp::from_raw([
+            0xa7b0_6105_b864_54f2,
+            0xc851_dd6a_6a68_075d,
+            0xe25f_0a29_903e_f27a,
+            0x33af_4a7a_8703_a6e6,
+        ]),
+        Fp::from_raw([
+            0xcab8_460f_d2ac_5a9a,
+            0xd336_175a_c503_f559,
+            0x5953_a135_7bb6_7b0b,
+            0x284f_fc55_0c0d_db08,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x94c4_1d5c_5a8f_8881,
+            0x8a75_b5f1_41ac_e4bf,
+            0x2137_5ff3_7f04_d15d,
+            0x30ba_06f7_4636_3530,
+        ]),
+        Fp::from_raw([
+            0xd169_b9e2_6fa5_9ba9,
+            0x1e03_113c_d185_5082,
+            0xf181_4e27_b9a4_30ac,
+            0x20c0_7f07_1ac2_7c62,
+        ]),
+        Fp::from_raw([
+            0x8011_da99_26ba_b61a,
+            0x7175_e69e_41a4_233e,
+            0x7ba9_8d06_ef3e_1432,
+            0x0cdb_3d49_f881_3956,
+        ]),
+        Fp::from_raw([
+            0x9ed1_8d21_56ad_8d31,
+            0x84c1_35a1_7d59_10f8,
+            0xfbed_7ee2_cd34_ec12,
+            0x06e3_64d7_31ae_cc54,
+        ]),
+        Fp::from_raw([
+            0xa3f1_92c5_6079_213e,
+            0x9c40_df3d_9d7f_0221,
+            0x5434_f959_5b36_c290,
+            0x2d37_0c96_cd06_47f3,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xa614_7d9b_4870_17ff,
+            0x9e9b_b998_0834_0187,
+            0x339b_667e_784f_479a,
+            0x2b85_76be_5916_3cd1,
+        ]),
+        Fp::from_raw([
+            0xb7d5_87af_eb8b_5534,
+            0x757d_b31b_3aab_ea2e,
+            0x315e_a67e_a3e8_5358,
+            0x2c0c_72b8_8f0a_c77a,
+        ]),
+        Fp::from_raw([
+            0x261f_fd4a_f85e_4850,
+            0xde4c_9174_15f2_3342,
+            0xa010_5f0a_41de_4350,
+            0x1c91_615f_82e7_9cec,
+        ]),
+        Fp::from_raw([
+            0xfd7c_b8e8_3376_2123,
+            0x18e0_2784_4a92_e304,
+            0xe496_f2d2_1de0_2e10,
+            0x3f21_d1f4_f86a_c311,
+        ]),
+        Fp::from_raw([
+            0x911a_14bf_8ae3_967d,
+            0x8920_54f4_31c5_a390,
+            0xc909_e64c_ee9b_0bfe,
+            0x1e75_994b_72f1_35d6,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2c0b_ecd6_fec3_904d,
+            0x1381_d090_322a_597e,
+            0x6f2d_46e6_45eb_c1c9,
+            0x165a_8ed2_274a_6ed6,
+        ]),
+        Fp::from_raw([
+            0x1081_baad_7b6b_c486,
+            0x366a_bb54_5605_89a3,
+            0xb075_72cb_3c4a_b694,
+            0x3bff_f536_613c_1030,
+        ]),
+        Fp::from_raw([
+            0xcf70_6a13_ed40_89e1,
+            0x2587_dae2_16dd_ec13,
+            0xdbbc_5dc9_4669_ccc8,
+            0x3b8f_0897_43eb_875b,
+        ]),
+        Fp::from_raw([
+            0x17cf_d145_22f2_4fc2,
+            0xb3c3_cbff_c1cf_459d,
+            0x2bff_9ad2_153f_994f,
+            0x0584_ec8f_06e3_b3e9,
+        ]),
+        Fp::from_raw([
+            0x271d_a652_6cf5_50ee,
+            0x9061_7ec9_0089_42ad,
+            0x6166_f930_c0f3_4fdc,
+            0x3158_cb75_fb82_3b64,
+        ]),
+    ],
+];
+
+// Secure MDS: 0, not checked against the algorithms of the reference implementation
+// n: 255
+// t: 5
+// Prime number: 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001
+// MDS matrix:
+pub(crate) const MDS: [[Fp; 5]; 5] = [
+    [
+        Fp::from_raw([
+            0xdd36_90b8_5d60_6db7,
+            0xb9fa_f6c4_21bc_ba62,
+            0xa450_7714_24a9_546c,
+            0x3e93_8526_162d_9a0d,
+        ]),
+        Fp::from_raw([
+            0xaf2a_c316_6f36_b55c,
+            0x805a_2b2e_188f_d633,
+            0x3870_4f17_7b04_9668,
+            0x2379_5b67_c401_f1ef,
+        ]),
+        Fp::from_raw([
+            0xef17_0b2f_76a7_a184,
+            0x765e_585c_fe87_d468,
+            0x1503_e483_ff4e_6ee5,
+            0x341e_cf03_5e9f_1099,
+        ]),
+        Fp::from_raw([
+            0x1151_692c_142d_b925,
+            0xfa94_a46c_e6f9_ce64,
+            0x3ff5_4cdc_e4da_b972,
+            0x1d7f_782a_0986_49e1,
+        ]),
+        Fp::from_raw([
+            0x1d38_18b4_13c8_fce0,
+            0x968c_f3aa_93c1_79d1,
+            0x8a16_152d_6430_0d62,
+            0x166e_55f9_b115_4525,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x63e2_41b8_4655_e1ff,
+            0xcd82_f022_ec31_7673,
+            0x22f6_a420_32fd_b3a1,
+            0x0f7c_06ef_e417_eb3b,
+        ]),
+        Fp::from_raw([
+            0x41ff_2850_8e30_3793,
+            0x545b_ef50_dd0a_8baf,
+            0xaf51_117a_c107_78f3,
+            0x1625_9e45_996a_85e5,
+        ]),
+        Fp::from_raw([
+            0x7b22_66e3_0480_40e4,
+            0x2700_83e5_b1d6_ae63,
+            0x2587_dd35_c9e0_eccc,
+            0x3392_4aba_7057_2e28,
+        ]),
+        Fp::from_raw([
+            0x1b4b_b52f_e5ed_6530,
+            0x47a0_a0d2_2fa1_381d,
+            0x1cef_aaf9_9c73_6178,
+            0x2783_31ba_7e6b_86a0,
+        ]),
+        Fp::from_raw([
+            0x4459_66e4_e205_36eb,
+            0xdd0b_9d55_00dd_8950,
+            0x6708_2207_238a_6c19,
+            0x166b_f477_71ea_1272,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xe2eb_dad0_0b06_4ff0,
+            0xbcc3_f446_589d_80cd,
+            0x026b_9308_3432_682a,
+            0x0f0c_e87b_7416_6642,
+        ]),
+        Fp::from_raw([
+            0xa829_a0f6_b09a_d21e,
+            0x6656_e3a5_ef09_cbd9,
+            0x1472_2d63_d53e_9b0c,
+            0x2569_d6bb_b22f_2d6e,
+        ]),
+        Fp::from_raw([
+            0x2a4b_4378_6222_ea1c,
+            0x3522_90f8_ea42_fade,
+            0x4b01_54d1_ea47_abc8,
+            0x0a6f_5ea8_46f3_86e0,
+        ]),
+        Fp::from_raw([
+            0xa218_fd3b_d1eb_87dc,
+            0xa6d4_bd6d_f44a_d214,
+            0xcebc_a77c_6c05_85f5,
+            0x2697_008e_a07e_6402,
+        ]),
+        Fp::from_raw([
+            0xd4a6_9b78_8648_1529,
+            0x0dc3_e08e_a23a_c47c,
+            0x3068_7856_c92e_8e5e,
+            0x2c46_6f9e_f67d_c1c7,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x6524_b24c_556a_d311,
+            0x1674_224f_b60c_96d2,
+            0x1149_1546_810e_81ac,
+            0x01db_fdb5_5edf_fb9c,
+        ]),
+        Fp::from_raw([
+            0x1907_8196_c3f4_6510,
+            0x7cef_caea_dc02_3aed,
+            0x5219_fbba_058f_d677,
+            0x1516_1846_99d5_b042,
+        ]),
+        Fp::from_raw([
+            0x0a33_4113_4201_829c,
+            0x3a94_6e56_2be1_be04,
+            0x9391_e523_e067_8fe3,
+            0x0373_cae9_bcec_0292,
+        ]),
+        Fp::from_raw([
+            0x31fd_c0e9_d1d1_5ae5,
+            0x1c08_e818_3076_b703,
+            0x7934_678f_1337_0333,
+            0x1700_829b_01ca_f429,
+        ]),
+        Fp::from_raw([
+            0xd467_ab52_926c_3ded,
+            0xcbca_51d5_5894_31ad,
+            0x89f6_bc20_33d3_6d3a,
+            0x09ee_1d2e_ad6b_e998,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x2f91_ae33_7486_a69e,
+            0xcbe3_3585_15d8_05f9,
+            0x6cd1_a071_1c3c_be95,
+            0x0a63_f180_073a_166e,
+        ]),
+        Fp::from_raw([
+            0xac52_2da5_4b7f_ad7f,
+            0x87d4_c8c9_674a_fea0,
+            0xbe0d_a49e_a226_d853,
+            0x2369_3f0b_8482_11b2,
+        ]),
+        Fp::from_raw([
+            0xaeae_f92e_3b49_8f6a,
+            0x3075_7e0d_88f0_56a4,
+            0x4fe3_b443_0eae_2234,
+            0x3bef_d22b_5ce5_78da,
+        ]),
+        Fp::from_raw([
+            0x7eff_e0cd_994d_3842,
+            0x93f1_13d7_f65d_1250,
+            0x60ac_b589_0e60_6abe,
+            0x273d_dbac_185c_a5f8,
+        ]),
+        Fp::from_raw([
+            0xa74d_29bc_37c3_7273,
+            0xc085_90b2_d7a8_9d3b,
+            0xdd7e_c017_bab2_46cb,
+            0x1654_d792_5b22_19be,
+        ]),
+    ],
+];
+
+// Inverse MDS matrix:
+pub(crate) const MDS_INV: [[Fp; 5]; 5] = [
+    [
+        Fp::from_raw([
+            0xfce9_23fc_8093_e207,
+            0x4f5a_04b2_fb76_b3cc,
+            0x1ed2_1d24_9745_cf45,
+            0x0257_c0a1_edd8_56c8,
+        ]),
+        Fp::from_raw([
+            0x7499_6774_3384_8a4d,
+            0xb22a_83f8_e8d5_c506,
+            0xf746_33dd_551b_3663,
+            0x1b4c_559b_beb6_c196,
+        ]),
+        Fp::from_raw([
+            0xa2d0_dec5_b1fc_1c87,
+            0xfa71_d842_a218_c513,
+            0x2389_a091_68d7_b58a,
+            0x08d4_d995_18b8_a47e,
+        ]),
+        Fp::from_raw([
+            0xbb36_63c3_e633_b1f2,
+            0xee41_0439_2ed0_c428,
+            0x85f6_b37e_d99f_6394,
+            0x2f1e_5511_9291_f2fd,
+        ]),
+        Fp::from_raw([
+            0xcf16_ca7a_00f8_5c15,
+            0x5844_4c11_00ac_7da7,
+            0xab13_961f_1242_18a3,
+            0x2054_87de_edbb_2e9b,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x51f8_8bae_8934_b1e5,
+            0x92f0_7ddb_cba2_ddfd,
+            0xa7b0_7f2d_9ebc_264f,
+            0x1f71_3177_591c_8657,
+        ]),
+        Fp::from_raw([
+            0x0334_e359_c716_7c5f,
+            0xd590_899f_ac2b_1e51,
+            0x14af_6f42_41e7_84d2,
+            0x3a49_3fb9_2df1_820c,
+        ]),
+        Fp::from_raw([
+            0xdda9_b207_b6c5_688d,
+            0xdf36_25d1_d5a8_5a1d,
+            0x2d4c_be0f_5346_c0b7,
+            0x17e9_0079_7c66_b6d4,
+        ]),
+        Fp::from_raw([
+            0xf284_60bc_255f_31cc,
+            0xc25a_af2d_49a9_cc29,
+            0x449e_9418_4139_8a9d,
+            0x06ea_98a4_85a5_1175,
+        ]),
+        Fp::from_raw([
+            0x226d_e37f_7590_e300,
+            0xf418_4bc4_faff_9ec3,
+            0x1e3c_bf62_eadf_a65c,
+            0x1871_f477_bb5b_f6b5,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0xf318_2b7f_8fe8_0c7d,
+            0x6f9d_4a95_25d0_e4ce,
+            0xb222_7fbb_1559_1031,
+            0x3de2_3268_9ea8_5e0b,
+        ]),
+        Fp::from_raw([
+            0x7638_5359_73b3_c162,
+            0x67fd_9194_d028_f0a3,
+            0x1e3a_16c5_b70d_009c,
+            0x0aa8_049e_a492_8d6c,
+        ]),
+        Fp::from_raw([
+            0x46df_71fc_cb6d_60d7,
+            0x3539_8c71_ca8d_1ef1,
+            0xec2d_8724_5316_0154,
+            0x2aa7_7e62_b399_6309,
+        ]),
+        Fp::from_raw([
+            0x1584_ddb9_def6_b1df,
+            0x5d88_a298_5429_82dd,
+            0xfade_f253_5fae_9e31,
+            0x09fe_e065_0fe5_1fcf,
+        ]),
+        Fp::from_raw([
+            0x0172_2400_33bf_114d,
+            0xea1d_5480_248e_5668,
+            0x18c8_a0ea_92fa_3eb9,
+            0x15ba_7a9a_6203_bfcd,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x790d_bb5c_92dd_0006,
+            0xd0a5_80ce_47a4_2f0a,
+            0xedb3_6c95_2fd6_51c7,
+            0x242f_b736_5b47_5d5a,
+        ]),
+        Fp::from_raw([
+            0x3776_28d7_b721_7c38,
+            0x96fa_b4c7_7dd4_149a,
+            0x51ee_6048_c819_91d6,
+            0x3102_ae8a_3699_9af9,
+        ]),
+        Fp::from_raw([
+            0x4e8a_75f7_68e1_987e,
+            0x589c_b0cc_11fc_1f54,
+            0x67fd_e220_f8b2_c140,
+            0x232b_6454_c1b9_2912,
+        ]),
+        Fp::from_raw([
+            0xe398_29c9_746c_4fbe,
+            0xddb7_b0be_bdf1_ef04,
+            0x7bed_ab82_44b7_aa16,
+            0x1608_0d11_0198_3b0c,
+        ]),
+        Fp::from_raw([
+            0x625e_7d7b_8f9b_ae4b,
+            0x92db_f8ad_eec4_bc84,
+            0x2912_2e7a_4789_bd5d,
+            0x11b1_9415_0c5f_05ab,
+        ]),
+    ],
+    [
+        Fp::from_raw([
+            0x1eb5_90db_0f83_f3c7,
+            0xa1c0_1e55_274b_0350,
+            0xfa86_c085_150c_11e1,
+            0x1e5b_985e_e36a_4026,
+        ]),
+        Fp::from_raw([
+            0x1e74_bdba_d49d_68cb,
+            0x763a_9e55_e3cf_f048,
+            0x8b3d_7b6b_74c1_41ab,
+            0x2612_fa23_7822_2c10,
+        ]),
+        Fp::from_raw([
+            0x5ef7_f817_fc3c_5baf,
+            0x46ee_57a8_a428_6de6,
+            0x068b_7d86_d12b_14a1,
+            0x38a8_c76c_7337_3b80,
+        ]),
+        Fp::from_raw([
+            0xc908_e322_6398_1e92,
+            0x7dfc_6620_5ae8_f933,
+            0x3851_a9dd_e41e_8e6d,
+            0x3d69_6c7e_3386_a040,
+        ]),
+        Fp::from_raw([
+            0xf1e7_4afb_cd9a_35d3,
+            0xb89c_786a_8c50_e0e9,
+            0xfa52_5ef9_3452_2953,
+            0x0a69_dcd2_5880_0fa6,
+        ]),
+    ],
+];
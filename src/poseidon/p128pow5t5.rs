@@ -0,0 +1,104 @@
+use crate::halo2::arithmetic::Field;
+use pasta_curves::pallas::Base as Fp;
+
+use super::{Mds, Spec};
+
+/// Poseidon-128 using the $x^5$ S-box, with a width of 5 field elements, suitable for a
+/// 4:1 reduction.
+///
+/// The standard number of rounds for this width on the Pasta fields is $R_F = 8, R_P = 60$.
+/// Only the Pallas base field is supported.
+#[derive(Debug)]
+pub struct P128Pow5T5;
+
+impl Spec<Fp, 5, 4> for P128Pow5T5 {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        60
+    }
+
+    fn sbox(val: Fp) -> Fp {
+        val.pow_vartime(&[5])
+    }
+
+    fn secure_mds(&self) -> usize {
+        unimplemented!()
+    }
+
+    fn constants(&self) -> (Vec<[Fp; 5]>, Mds<Fp, 5>, Mds<Fp, 5>) {
+        (
+            super::fp5::ROUND_CONSTANTS[..].to_vec(),
+            super::fp5::MDS,
+            super::fp5::MDS_INV,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::halo2::arithmetic::{Field, FieldExt};
+
+    use super::{super::fp5, Fp, P128Pow5T5};
+    use crate::poseidon::{permute, ConstantLength, Hash, Spec};
+
+    /// The same specification as `P128Pow5T5`, with constants generated at runtime.
+    #[derive(Debug)]
+    struct P128Pow5T5Gen;
+
+    impl Spec<Fp, 5, 4> for P128Pow5T5Gen {
+        fn full_rounds() -> usize {
+            8
+        }
+
+        fn partial_rounds() -> usize {
+            60
+        }
+
+        fn sbox(val: Fp) -> Fp {
+            val.pow_vartime(&[5])
+        }
+
+        fn secure_mds(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn verify_constants() {
+        let (round_constants, mds, mds_inv) = P128Pow5T5Gen.constants();
+
+        assert_eq!(round_constants.len(), fp5::ROUND_CONSTANTS.len());
+        for (actual, expected) in round_constants
+            .iter()
+            .flatten()
+            .zip(fp5::ROUND_CONSTANTS.iter().flatten())
+        {
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(mds, fp5::MDS);
+        assert_eq!(mds_inv, fp5::MDS_INV);
+
+        // the inverse is the inverse
+        for i in 0..5 {
+            for j in 0..5 {
+                let entry = (0..5).fold(Fp::zero(), |acc, k| acc + mds[i][k] * mds_inv[k][j]);
+                assert_eq!(entry, if i == j { Fp::one() } else { Fp::zero() });
+            }
+        }
+    }
+
+    #[test]
+    fn hash_is_permutation() {
+        let message = [Fp::from(1), Fp::from(2), Fp::from(3), Fp::from(4)];
+
+        let result = Hash::init(P128Pow5T5, ConstantLength).hash(message);
+
+        let (round_constants, mds, _) = P128Pow5T5.constants();
+        let mut state = [message[0], message[1], message[2], message[3], Fp::from_u128(4 << 64)];
+        permute::<_, P128Pow5T5, 5, 4>(&mut state, &mds, &round_constants);
+        assert_eq!(state[0], result);
+    }
+}
@@ -1,171 +1,538 @@
 use crate::halo2::{
     plonk,
-    plonk::{Error},
-    transcript::{Blake2bRead, Blake2bWrite},
+    poly::commitment::MSM,
 };
 
+use ff::{Field, PrimeField};
+use rand::rngs::OsRng;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::marker::PhantomData;
+
 use pasta_curves::{
     vesta, 
     pallas::Base as Fp
 };
 
 use crate::{
+    error::Error,
     keys::{ProvingKey, VerifyingKey},
-    circuit::{Circuit, Y, NULLIFIER, SIGNAL, ROOT},
+    merkle::RootPolicy,
+    utils::{from_hex, to_hex},
+    transcript::{Blake2bTranscript, TranscriptScheme},
+    circuit::{Circuit, Y, NULLIFIER, SIGNAL, ROOT, RLN_IDENTIFIER, EPOCH},
 };
 
-#[derive(Clone, Debug, Default)]
+/// Length of the canonical encoding of an [`Instance`].
+pub const INSTANCE_LEN: usize = 6 * 32;
+
+/// Canonical encoding of a field element, 32 bytes little-endian.
+pub fn field_to_bytes(value: &Fp) -> [u8; 32] {
+    value.to_repr()
+}
+
+/// Decodes a field element, rejecting values at or above the modulus.
+pub fn field_from_bytes(bytes: &[u8; 32]) -> Result<Fp, Error> {
+    Option::from(Fp::from_repr(*bytes)).ok_or(Error::InvalidPublicInputs)
+}
+
+/// Public inputs of a proof.
+///
+/// The canonical encoding is the six fields in the order of the circuit instance
+/// column, each in the encoding of [`field_to_bytes`]. The hex encoding and the
+/// serde one, behind the `serde` feature, use the same bytes.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Instance {
     pub y: Fp, 
     pub nullifier: Fp,
     pub signal: Fp,
-    pub root: Fp
+    pub root: Fp,
+    pub rln_identifier: Fp,
+    pub epoch: Fp
 }
 
 impl Instance {
-    pub fn to_halo2_instance(&self) -> [[Fp; 4]; 1] {
-        let mut instance = [Fp::zero(); 4];
+    pub fn to_halo2_instance(&self) -> [[Fp; 6]; 1] {
+        let mut instance = [Fp::zero(); 6];
 
         instance[Y] = self.y;
         instance[NULLIFIER] = self.nullifier;
         instance[SIGNAL] = self.signal;
         instance[ROOT] = self.root;
+        instance[RLN_IDENTIFIER] = self.rln_identifier;
+        instance[EPOCH] = self.epoch;
 
         [instance]
     }
+
+    fn from_inputs(inputs: [Fp; 6]) -> Self {
+        Instance {
+            y: inputs[Y],
+            nullifier: inputs[NULLIFIER],
+            signal: inputs[SIGNAL],
+            root: inputs[ROOT],
+            rln_identifier: inputs[RLN_IDENTIFIER],
+            epoch: inputs[EPOCH],
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; INSTANCE_LEN] {
+        let mut bytes = [0u8; INSTANCE_LEN];
+        for (chunk, input) in bytes.chunks_mut(32).zip(self.to_halo2_instance()[0].iter()) {
+            chunk.copy_from_slice(&field_to_bytes(input));
+        }
+        bytes
+    }
+
+    /// Decodes an instance of exactly [`INSTANCE_LEN`] bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != INSTANCE_LEN {
+            return Err(Error::InvalidPublicInputs);
+        }
+
+        let mut inputs = [Fp::zero(); 6];
+        for (input, chunk) in inputs.iter_mut().zip(bytes.chunks(32)) {
+            let mut encoded = [0u8; 32];
+            encoded.copy_from_slice(chunk);
+            *input = field_from_bytes(&encoded)?;
+        }
+        Ok(Self::from_inputs(inputs))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.to_halo2_instance()[0].iter().map(|input| to_hex(&field_to_bytes(input))).collect()
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        if hex.len() != 2 * INSTANCE_LEN || !hex.is_ascii() {
+            return Err(Error::InvalidPublicInputs);
+        }
+
+        let mut inputs = [Fp::zero(); 6];
+        for (i, input) in inputs.iter_mut().enumerate() {
+            let encoded = from_hex(&hex[64 * i..64 * (i + 1)]).ok_or(Error::InvalidPublicInputs)?;
+            *input = field_from_bytes(&encoded)?;
+        }
+        Ok(Self::from_inputs(inputs))
+    }
+}
+
+/// Instance with every field as the hex of its canonical encoding.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct InstanceJson {
+    y: String,
+    nullifier: String,
+    signal: String,
+    root: String,
+    rln_identifier: String,
+    epoch: String,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Instance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = |value: &Fp| to_hex(&field_to_bytes(value));
+        InstanceJson {
+            y: hex(&self.y),
+            nullifier: hex(&self.nullifier),
+            signal: hex(&self.signal),
+            root: hex(&self.root),
+            rln_identifier: hex(&self.rln_identifier),
+            epoch: hex(&self.epoch),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Instance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = InstanceJson::deserialize(deserializer)?;
+        let field = |hex: &str| {
+            from_hex(hex)
+                .ok_or(Error::InvalidPublicInputs)
+                .and_then(|bytes| field_from_bytes(&bytes))
+                .map_err(de::Error::custom)
+        };
+
+        Ok(Instance {
+            y: field(&json.y)?,
+            nullifier: field(&json.nullifier)?,
+            signal: field(&json.signal)?,
+            root: field(&json.root)?,
+            rln_identifier: field(&json.rln_identifier)?,
+            epoch: field(&json.epoch)?,
+        })
+    }
 }
 
 
+/// Proof bytes, made with the transcript `T`.
+///
+/// A proof only verifies with the transcript it was made with.
 #[derive(Debug, Clone)]
-pub struct Proof(Vec<u8>);
+pub struct Proof<const DEPTH: usize, T = Blake2bTranscript>(Vec<u8>, PhantomData<T>);
 
-impl AsRef<[u8]> for Proof {
+impl<const DEPTH: usize, T> AsRef<[u8]> for Proof<DEPTH, T> {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
-impl Proof {
+/// Public inputs of each instance in the layout expected by halo2.
+fn halo2_instances(instances: &[Instance]) -> Vec<[[Fp; 6]; 1]> {
+    instances.iter().map(|i| i.to_halo2_instance()).collect()
+}
+
+impl<const DEPTH: usize> Proof<DEPTH> {
     /// Creates a proof for the given circuit and instances.
     pub fn create_raw(
-        pk: &ProvingKey,
-        circuits: &[Circuit],
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
         instances: &[Instance],
     ) -> Result<Vec<u8>, Error> {
-        let instances: Vec<_> = instances.iter().map(|i| i.to_halo2_instance()).collect();
+        Ok(Proof::<DEPTH>::prove(pk, circuits, instances)?.0)
+    }
+
+    pub fn create(
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
+        instances: &[Instance],
+    ) -> Result<Self, Error> {
+        Proof::prove(pk, circuits, instances)
+    }
+
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Proof::from_raw(bytes)
+    }
+}
+
+impl<const DEPTH: usize, T: TranscriptScheme> Proof<DEPTH, T> {
+    /// Creates a proof for the given circuits and instances with the transcript `T`.
+    pub fn prove(
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
+        instances: &[Instance],
+    ) -> Result<Self, Error> {
+        let instances = halo2_instances(instances);
         let instances: Vec<Vec<_>> = instances
             .iter()
             .map(|i| i.iter().map(|c| &c[..]).collect())
             .collect();
         let public_inputs: Vec<_> = instances.iter().map(|i| &i[..]).collect();
 
-        let mut transcript = Blake2bWrite::<_, vesta::Affine, _>::init(vec![]);
-        plonk::create_proof(&pk.params, &pk.pk, &circuits, &public_inputs, &mut transcript)?;
-        Ok(transcript.finalize())
+        let raw = T::create_proof(pk, circuits, &public_inputs)?;
+        Ok(Proof(raw, PhantomData))
     }
-    pub fn create(
-        pk: &ProvingKey,
-        circuits: &[Circuit],
-        instances: &[Instance],
-    ) -> Result<Self, Error> {
-        let raw = Proof::create_raw(pk, circuits, instances)?;
-        Ok(Proof(raw))
+
+    /// Wraps proof bytes made with the transcript `T`.
+    pub fn from_raw(bytes: Vec<u8>) -> Self {
+        Proof(bytes, PhantomData)
+    }
+
+    /// Verifies the proof for `instances`, whose roots must be accepted by `roots`.
+    ///
+    /// A single `Fp` accepts only that root, an `IncrementalTree` or `RootHistory`
+    /// accepts any of its recent roots. Root mismatches are reported as [`Error::UnknownRoot`] before any cryptographic
+    /// work is done, undecodable proofs as [`Error::MalformedProof`] and failed checks
+    /// as [`Error::VerificationFailed`].
+    pub fn verify<P: RootPolicy + ?Sized>(&self, vk: &VerifyingKey<DEPTH>, instances: &[Instance], roots: &P) -> Result<(), Error> {
+        let msm = self.verify_msm(vk, instances, roots, vk.params.empty_msm())?;
+        if msm.eval() {
+            Ok(())
+        } else {
+            Err(Error::VerificationFailed)
+        }
     }
 
-    pub fn verify(&self, vk: &VerifyingKey, instances: &[Instance]) -> Result<(), plonk::Error> {
-        let instances: Vec<_> = instances.iter().map(|i| i.to_halo2_instance()).collect();
+    /// Runs the verifier on top of `msm` and returns the MSM that is left to check.
+    fn verify_msm<'params, P: RootPolicy + ?Sized>(
+        &self,
+        vk: &'params VerifyingKey<DEPTH>,
+        instances: &[Instance],
+        roots: &P,
+        msm: MSM<'params, vesta::Affine>,
+    ) -> Result<MSM<'params, vesta::Affine>, Error> {
+        if instances.iter().any(|instance| !roots.is_acceptable_root(&instance.root)) {
+            return Err(Error::UnknownRoot);
+        }
+
+        let instances = halo2_instances(instances);
         let instances: Vec<Vec<_>> = instances
             .iter()
             .map(|i| i.iter().map(|c| &c[..]).collect())
             .collect();
         let instances: Vec<_> = instances.iter().map(|i| &i[..]).collect();
 
-        let msm = vk.params.empty_msm();
-        let mut transcript = Blake2bRead::init(&self.0[..]);
-        let guard = plonk::verify_proof(&vk.params, &vk.vk, msm, &instances, &mut transcript)?;
-        let msm = guard.clone().use_challenges();
-        if msm.eval() {
+        T::verify_proof(&vk.params, &vk.vk, msm, &instances, &self.0[..]).map_err(verification_error)
+    }
+}
+
+/// Verifies many proofs with one combined multiexponentiation.
+///
+/// The MSM of each proof is folded into a running one after scaling it by a random
+/// factor, so a single evaluation checks the whole batch. If the batch fails, the
+/// proofs are checked one by one to find the bad ones.
+#[derive(Debug)]
+pub struct BatchVerifier<'a, const DEPTH: usize, T = Blake2bTranscript> {
+    vk: &'a VerifyingKey<DEPTH>,
+    items: Vec<(Proof<DEPTH, T>, Vec<Instance>)>,
+}
+
+impl<'a, const DEPTH: usize, T: TranscriptScheme> BatchVerifier<'a, DEPTH, T> {
+    pub fn new(vk: &'a VerifyingKey<DEPTH>) -> Self {
+        BatchVerifier { vk, items: vec![] }
+    }
+
+    /// Queues `proof` to be checked for `instances`.
+    pub fn add(&mut self, proof: Proof<DEPTH, T>, instances: Vec<Instance>) {
+        self.items.push((proof, instances));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Checks every queued proof, accepting the roots allowed by `roots`.
+    ///
+    /// On failure returns the index of each bad proof, in the order they were added,
+    /// together with the reason it was rejected.
+    pub fn finalize<P: RootPolicy + ?Sized>(self, roots: &P) -> Result<(), Vec<(usize, Error)>> {
+        let mut failures = vec![];
+        let mut msm = self.vk.params.empty_msm();
+
+        for (index, (proof, instances)) in self.items.iter().enumerate() {
+            msm.scale(Fp::random(OsRng));
+            match proof.verify_msm(self.vk, instances, roots, msm.clone()) {
+                Ok(accumulated) => msm = accumulated,
+                Err(err) => failures.push((index, err)),
+            }
+        }
+
+        if !msm.eval() {
+            return Err(self.verify_each(roots));
+        }
+
+        if failures.is_empty() {
             Ok(())
         } else {
-            Err(plonk::Error::ConstraintSystemFailure)
+            Err(failures)
         }
     }
 
-    pub fn new(bytes: Vec<u8>) -> Self {
-        Proof(bytes)
+    fn verify_each<P: RootPolicy + ?Sized>(&self, roots: &P) -> Vec<(usize, Error)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (proof, instances))| {
+                proof.verify(self.vk, instances, roots).err().map(|err| (index, err))
+            })
+            .collect()
+    }
+}
+
+/// Classifies a `plonk::verify_proof` failure.
+fn verification_error(err: plonk::Error) -> Error {
+    match err {
+        plonk::Error::Transcript(_) => Error::MalformedProof,
+        plonk::Error::InvalidInstances | plonk::Error::InstanceTooLarge => Error::InvalidPublicInputs,
+        plonk::Error::ConstraintSystemFailure | plonk::Error::Opening => Error::VerificationFailed,
+        err => Error::Plonk(err),
     }
 }
 
-/*
 #[cfg(test)]
 mod tests {
-    // use wasm_bindgen_test::*;
-
     use ff::Field;
-    use std::iter;
-    use halo2::pasta::Fp;
-    use halo2::dev::MockProver;
+    use crate::halo2::pasta::Fp;
 
+    use crate::client::{rate_commitment, SignalRequest};
     use crate::circuit::Circuit;
-    use crate::keys::{ProvingKey, VerifyingKey, K};
-    use rand::Rng;
+    use crate::keys::{ProvingKey, VerifyingKey};
+    use crate::merkle::IncrementalTree;
+
+    use crate::error::Error;
+
+    use super::{BatchVerifier, Instance, Proof, INSTANCE_LEN};
+    use crate::transcript::{Blake2bTranscript, PoseidonTranscript};
+
+    const K: u32 = 11;
+    const DEPTH: usize = 16;
+
+    fn circuit_and_instance() -> (Circuit<DEPTH>, Instance) {
+        let mut rng = rand::thread_rng();
+        let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
+
+        let secret = Fp::random(&mut rng);
+        tree.insert(rate_commitment(secret, Fp::from(1)));
+
+        member_circuit_and_instance(&mut tree, secret)
+    }
+
+    /// Builds the first signal of a random epoch for a member already in `tree`.
+    fn member_circuit_and_instance(tree: &mut IncrementalTree, secret: Fp) -> (Circuit<DEPTH>, Instance) {
+        let mut rng = rand::thread_rng();
+
+        SignalRequest {
+            secret,
+            user_message_limit: Fp::from(1),
+            message_id: Fp::from(0),
+            epoch: Fp::random(&mut rng),
+            rln_identifier: Fp::random(&mut rng),
+            signal: "hello rln".to_string(),
+        }
+        .witness(tree)
+        .unwrap()
+    }
+
+    #[test]
+    fn instance_encoding() {
+        let (_, instance) = circuit_and_instance();
+
+        let bytes = instance.to_bytes();
+        assert_eq!(bytes.len(), INSTANCE_LEN);
+        assert_eq!(&bytes[96..128], &super::field_to_bytes(&instance.root));
+        assert_eq!(Instance::from_bytes(&bytes).unwrap(), instance);
+        assert_eq!(Instance::from_hex(&instance.to_hex()).unwrap(), instance);
 
-    use super::{Instance, Proof};
+        // p - 1 is the largest canonical value, p and above are rejected
+        let mut largest = bytes;
+        largest[32..64].copy_from_slice(&super::field_to_bytes(&-Fp::one()));
+        assert_eq!(Instance::from_bytes(&largest).unwrap().nullifier, -Fp::one());
+        let mut modulus = largest;
+        modulus[32] += 1;
+        assert!(matches!(Instance::from_bytes(&modulus), Err(Error::InvalidPublicInputs)));
+        let mut above = largest;
+        above[63] = 0xff;
+        assert!(matches!(Instance::from_bytes(&above), Err(Error::InvalidPublicInputs)));
+
+        let modulus_hex: String = modulus.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert!(matches!(Instance::from_hex(&modulus_hex), Err(Error::InvalidPublicInputs)));
+
+        assert!(matches!(Instance::from_bytes(&bytes[1..]), Err(Error::InvalidPublicInputs)));
+        assert!(matches!(Instance::from_hex(&instance.to_hex()[2..]), Err(Error::InvalidPublicInputs)));
+        assert!(matches!(Instance::from_hex(&"zz".repeat(INSTANCE_LEN)), Err(Error::InvalidPublicInputs)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn instance_json() {
+        let (_, instance) = circuit_and_instance();
+
+        let json = serde_json::to_string(&instance).unwrap();
+        assert_eq!(serde_json::from_str::<Instance>(&json).unwrap(), instance);
+
+        // the nullifier set to the field modulus
+        let mut modulus = super::field_to_bytes(&-Fp::one());
+        modulus[0] += 1;
+        let modulus_hex: String = modulus.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let json = json.replace(&instance.to_hex()[64..128], &modulus_hex);
+        assert!(serde_json::from_str::<Instance>(&json).is_err());
+    }
 
     #[test]
     fn round_trip() {
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let vk = VerifyingKey::<DEPTH>::build(K);
+
+        let (circuit, instance) = circuit_and_instance();
+        let proof = Proof::create(&pk, &[circuit], &[instance.clone()]).unwrap();
+        assert!(proof.verify(&vk, &[instance.clone()], &instance.root).is_ok());
+
+        // a proof for one epoch must not verify for another
+        let mut other_epoch = instance.clone();
+        other_epoch.epoch += Fp::one();
+        assert!(matches!(proof.verify(&vk, &[other_epoch], &instance.root), Err(Error::VerificationFailed)));
+
+        let mut other_root = instance.clone();
+        other_root.root += Fp::one();
+        assert!(matches!(proof.verify(&vk, &[other_root.clone()], &other_root.root), Err(Error::VerificationFailed)));
+        assert!(matches!(proof.verify(&vk, &[instance.clone()], &other_root.root), Err(Error::UnknownRoot)));
+
+        let truncated = Proof::<DEPTH>::new(proof.as_ref()[..proof.as_ref().len() / 2].to_vec());
+        assert!(matches!(truncated.verify(&vk, &[instance.clone()], &instance.root), Err(Error::MalformedProof)));
+    }
+
+    #[test]
+    fn transcripts() {
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let vk = VerifyingKey::<DEPTH>::build(K);
+
+        let (circuit, instance) = circuit_and_instance();
+        let poseidon = Proof::<DEPTH, PoseidonTranscript>::prove(&pk, &[circuit.clone()], &[instance.clone()]).unwrap();
+        assert!(poseidon.verify(&vk, &[instance.clone()], &instance.root).is_ok());
+
+        let mut batch = BatchVerifier::<DEPTH, PoseidonTranscript>::new(&vk);
+        batch.add(poseidon.clone(), vec![instance.clone()]);
+        assert!(batch.finalize(&instance.root).is_ok());
+
+        // the same bytes read with the other transcript give other challenges
+        let as_blake2b = Proof::<DEPTH, Blake2bTranscript>::from_raw(poseidon.as_ref().to_vec());
+        assert!(matches!(as_blake2b.verify(&vk, &[instance.clone()], &instance.root), Err(Error::VerificationFailed)));
+
+        let blake2b = Proof::create(&pk, &[circuit], &[instance.clone()]).unwrap();
+        let as_poseidon = Proof::<DEPTH, PoseidonTranscript>::from_raw(blake2b.as_ref().to_vec());
+        assert!(matches!(as_poseidon.verify(&vk, &[instance.clone()], &instance.root), Err(Error::VerificationFailed)));
+    }
+
+    #[test]
+    fn batch() {
         let mut rng = rand::thread_rng();
+        let pk = ProvingKey::<DEPTH>::build(K);
+        let vk = VerifyingKey::<DEPTH>::build(K);
 
-        let (circuits, instances): (Vec<_>, Vec<_>) = iter::once(())
-            .map(|()| {
-                let a = Fp::random(&mut rng);
-                let b = Fp::random(&mut rng);
-                let num: u64 = rand::thread_rng().gen_range(0..1);
-                let selector = Fp::from(num);
-
-                let result;
-                if selector == Fp::one() {
-                    result = 1 as u64;
-                } else {
-                    result = 0 as u64;
-                }
-
-                (
-                    MuxCircuit::<Fp> {
-                        a: Some(a), 
-                        b: Some(b), 
-                        selector: Some(selector)
-                    },
-                    Instance {
-                        result
-                    },
-                )
+        // one member signalling in several epochs
+        let mut tree = IncrementalTree::new(Fp::zero(), DEPTH);
+        let secret = Fp::random(&mut rng);
+        tree.insert(rate_commitment(secret, Fp::from(1)));
+        let root = tree.root();
+
+        let proofs: Vec<_> = (0..3)
+            .map(|_| {
+                let (circuit, instance) = member_circuit_and_instance(&mut tree, secret);
+                let proof = Proof::create(&pk, &[circuit], &[instance.clone()]).unwrap();
+                (proof, instance)
             })
-            .unzip();
-
-        let vk = VerifyingKey::build();
-
-        for (circuit, instance) in circuits.iter().zip(instances.iter()) {
-            assert_eq!(
-                MockProver::run(
-                    K,
-                    circuit,
-                    instance
-                        .to_halo2_instance()
-                        .iter()
-                        .map(|p| p.to_vec())
-                        .collect()
-                )
-                .unwrap()
-                .verify(),
-                Ok(())
-            );
+            .collect();
+
+        let mut batch = BatchVerifier::new(&vk);
+        for (proof, instance) in proofs.iter() {
+            batch.add(proof.clone(), vec![instance.clone()]);
         }
+        assert_eq!(batch.len(), 3);
+        assert!(batch.finalize(&root).is_ok());
 
-        let pk = ProvingKey::build();
-        let proof = Proof::create(&pk, &circuits, &instances).unwrap();
-        assert!(proof.verify(&vk, &instances).is_ok());
-    }
+        // a bad proof is found by the fallback, the other proofs are not blamed
+        let mut batch = BatchVerifier::new(&vk);
+        for (index, (proof, instance)) in proofs.iter().enumerate() {
+            let mut instance = instance.clone();
+            if index == 1 {
+                instance.epoch += Fp::one();
+            }
+            batch.add(proof.clone(), vec![instance]);
+        }
+        let failures = batch.finalize(&root).unwrap_err();
+        assert_eq!(failures.len(), 1);
+        assert!(matches!(failures[0], (1, Error::VerificationFailed)));
 
+        // proofs for an unknown root are rejected without touching the rest
+        let mut batch = BatchVerifier::new(&vk);
+        let (circuit, instance) = circuit_and_instance();
+        let other_root = Proof::create(&pk, &[circuit], &[instance.clone()]).unwrap();
+        batch.add(proofs[0].0.clone(), vec![proofs[0].1.clone()]);
+        batch.add(other_root, vec![instance]);
+        batch.add(Proof::new(vec![0; 8]), vec![proofs[2].1.clone()]);
+        // members joined after the proofs were made, the tree still accepts their root
+        tree.insert(rate_commitment(Fp::random(&mut rng), Fp::from(1)));
+        assert_ne!(root, tree.root());
+        let failures = batch.finalize(&tree).unwrap_err();
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(failures[0], (1, Error::UnknownRoot)));
+        assert!(matches!(failures[1], (2, Error::MalformedProof)));
+    }
 }
-*/
\ No newline at end of file
@@ -0,0 +1,300 @@
+/*
+Fiat-Shamir transcripts a `Proof` can be made with.
+
+`Blake2bTranscript` is the halo2 default. `PoseidonTranscript` hashes the transcript
+with the `P128Pow5T3` duplex sponge over `vesta::Base`, the field the proof points are
+defined over, so a circuit checking the proof on the other curve of the cycle can
+recompute the challenges natively.
+
+Every message to the sponge starts with a tag element, as the Blake2b transcript
+prefixes its messages with a byte: a point absorbs its two coordinates, a scalar of
+`vesta::Scalar` is smaller than the modulus of `vesta::Base` and is absorbed as is. A
+challenge is one squeezed element, reduced into the scalar field.
+*/
+use crate::halo2::{
+    arithmetic::{Coordinates, CurveAffine, FieldExt},
+    plonk,
+    poly::commitment::{Params, MSM},
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255, EncodedChallenge, Transcript, TranscriptRead, TranscriptWrite},
+};
+use crate::poseidon::{Duplex, P128Pow5T3};
+use crate::circuit::Circuit;
+use crate::keys::ProvingKey;
+use ff::PrimeField;
+use pasta_curves::{vesta, pallas::Base as Fp};
+use std::io::{self, Read, Write};
+
+type Fq = vesta::Base;
+
+const TAG_CHALLENGE: u64 = 0;
+const TAG_POINT: u64 = 1;
+const TAG_SCALAR: u64 = 2;
+
+/// Fiat-Shamir transcript used to create and verify a [`crate::proof::Proof`].
+pub trait TranscriptScheme {
+    /// Proves `circuits` for `instances` and returns the transcript bytes.
+    fn create_proof<const DEPTH: usize>(
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
+        instances: &[&[&[Fp]]],
+    ) -> Result<Vec<u8>, plonk::Error>;
+
+    /// Runs the verifier over `proof` on top of `msm` and returns the MSM left to check.
+    fn verify_proof<'params>(
+        params: &'params Params<vesta::Affine>,
+        vk: &plonk::VerifyingKey<vesta::Affine>,
+        msm: MSM<'params, vesta::Affine>,
+        instances: &[&[&[Fp]]],
+        proof: &[u8],
+    ) -> Result<MSM<'params, vesta::Affine>, plonk::Error>;
+}
+
+/// The halo2 Blake2b transcript.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Blake2bTranscript;
+
+impl TranscriptScheme for Blake2bTranscript {
+    fn create_proof<const DEPTH: usize>(
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
+        instances: &[&[&[Fp]]],
+    ) -> Result<Vec<u8>, plonk::Error> {
+        let mut transcript = Blake2bWrite::<_, vesta::Affine, _>::init(vec![]);
+        plonk::create_proof(&pk.params, &pk.pk, circuits, instances, &mut transcript)?;
+        Ok(transcript.finalize())
+    }
+
+    fn verify_proof<'params>(
+        params: &'params Params<vesta::Affine>,
+        vk: &plonk::VerifyingKey<vesta::Affine>,
+        msm: MSM<'params, vesta::Affine>,
+        instances: &[&[&[Fp]]],
+        proof: &[u8],
+    ) -> Result<MSM<'params, vesta::Affine>, plonk::Error> {
+        let mut transcript = Blake2bRead::init(proof);
+        let guard = plonk::verify_proof(params, vk, msm, instances, &mut transcript)?;
+        Ok(guard.use_challenges())
+    }
+}
+
+/// Transcript hashed with the Poseidon duplex sponge.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PoseidonTranscript;
+
+impl TranscriptScheme for PoseidonTranscript {
+    fn create_proof<const DEPTH: usize>(
+        pk: &ProvingKey<DEPTH>,
+        circuits: &[Circuit<DEPTH>],
+        instances: &[&[&[Fp]]],
+    ) -> Result<Vec<u8>, plonk::Error> {
+        let mut transcript = PoseidonWrite::init(vec![]);
+        plonk::create_proof(&pk.params, &pk.pk, circuits, instances, &mut transcript)?;
+        Ok(transcript.finalize())
+    }
+
+    fn verify_proof<'params>(
+        params: &'params Params<vesta::Affine>,
+        vk: &plonk::VerifyingKey<vesta::Affine>,
+        msm: MSM<'params, vesta::Affine>,
+        instances: &[&[&[Fp]]],
+        proof: &[u8],
+    ) -> Result<MSM<'params, vesta::Affine>, plonk::Error> {
+        let mut transcript = PoseidonRead::init(proof);
+        let guard = plonk::verify_proof(params, vk, msm, instances, &mut transcript)?;
+        Ok(guard.use_challenges())
+    }
+}
+
+/// Sponge state shared by the reading and writing sides.
+struct PoseidonSponge(Duplex<Fq, P128Pow5T3, 3, 2>);
+
+impl PoseidonSponge {
+    fn new() -> Self {
+        // the capacity element separates the transcript from other uses of the sponge
+        let domain = Fq::from_u128(u128::from_le_bytes(*b"Halo2-Transcript"));
+        let pad_and_add = Box::new(|state: &mut [Fq; 3], input: &[Option<Fq>; 2]| {
+            for (word, value) in state.iter_mut().zip(input.iter()) {
+                if let Some(value) = value {
+                    *word += value;
+                }
+            }
+        });
+        PoseidonSponge(Duplex::new(P128Pow5T3, domain, pad_and_add))
+    }
+
+    fn squeeze_challenge(&mut self) -> Challenge255<vesta::Affine> {
+        self.0.absorb(Fq::from(TAG_CHALLENGE));
+        let mut input = [0u8; 64];
+        input[..32].copy_from_slice(&self.0.squeeze().to_repr());
+        Challenge255::new(&input)
+    }
+
+    fn common_point(&mut self, point: vesta::Affine) -> io::Result<()> {
+        let coords: Coordinates<_> = Option::from(point.coordinates()).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Other, "cannot write points at infinity to the transcript")
+        })?;
+        self.0.absorb(Fq::from(TAG_POINT));
+        self.0.absorb(*coords.x());
+        self.0.absorb(*coords.y());
+        Ok(())
+    }
+
+    fn common_scalar(&mut self, scalar: Fp) -> io::Result<()> {
+        // the scalar field modulus is below the base field one
+        let scalar = Option::from(Fq::from_repr(scalar.to_repr())).expect("scalar fits in the base field");
+        self.0.absorb(Fq::from(TAG_SCALAR));
+        self.0.absorb(scalar);
+        Ok(())
+    }
+}
+
+fn read_point<C: CurveAffine, R: Read>(reader: &mut R) -> io::Result<C> {
+    let mut compressed = C::Repr::default();
+    reader.read_exact(compressed.as_mut())?;
+    Option::from(C::from_bytes(&compressed))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "invalid point encoding in proof"))
+}
+
+fn point_bytes<C: CurveAffine>(point: &C) -> C::Repr {
+    point.to_bytes()
+}
+
+/// Poseidon transcript reading a proof.
+pub struct PoseidonRead<R: Read> {
+    sponge: PoseidonSponge,
+    reader: R,
+}
+
+impl<R: Read> PoseidonRead<R> {
+    pub fn init(reader: R) -> Self {
+        PoseidonRead { sponge: PoseidonSponge::new(), reader }
+    }
+}
+
+impl<R: Read> Transcript<vesta::Affine, Challenge255<vesta::Affine>> for PoseidonRead<R> {
+    fn squeeze_challenge(&mut self) -> Challenge255<vesta::Affine> {
+        self.sponge.squeeze_challenge()
+    }
+
+    fn common_point(&mut self, point: vesta::Affine) -> io::Result<()> {
+        self.sponge.common_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: Fp) -> io::Result<()> {
+        self.sponge.common_scalar(scalar)
+    }
+}
+
+impl<R: Read> TranscriptRead<vesta::Affine, Challenge255<vesta::Affine>> for PoseidonRead<R> {
+    fn read_point(&mut self) -> io::Result<vesta::Affine> {
+        let point = read_point(&mut self.reader)?;
+        self.common_point(point)?;
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<Fp> {
+        let mut repr = [0u8; 32];
+        self.reader.read_exact(&mut repr)?;
+        let scalar = Option::from(Fp::from_repr(repr))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "invalid field element encoding in proof"))?;
+        self.common_scalar(scalar)?;
+        Ok(scalar)
+    }
+}
+
+/// Poseidon transcript writing a proof.
+pub struct PoseidonWrite<W: Write> {
+    sponge: PoseidonSponge,
+    writer: W,
+}
+
+impl<W: Write> PoseidonWrite<W> {
+    pub fn init(writer: W) -> Self {
+        PoseidonWrite { sponge: PoseidonSponge::new(), writer }
+    }
+
+    /// Returns the writer holding the proof.
+    pub fn finalize(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Transcript<vesta::Affine, Challenge255<vesta::Affine>> for PoseidonWrite<W> {
+    fn squeeze_challenge(&mut self) -> Challenge255<vesta::Affine> {
+        self.sponge.squeeze_challenge()
+    }
+
+    fn common_point(&mut self, point: vesta::Affine) -> io::Result<()> {
+        self.sponge.common_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: Fp) -> io::Result<()> {
+        self.sponge.common_scalar(scalar)
+    }
+}
+
+impl<W: Write> TranscriptWrite<vesta::Affine, Challenge255<vesta::Affine>> for PoseidonWrite<W> {
+    fn write_point(&mut self, point: vesta::Affine) -> io::Result<()> {
+        self.common_point(point)?;
+        self.writer.write_all(point_bytes(&point).as_ref())
+    }
+
+    fn write_scalar(&mut self, scalar: Fp) -> io::Result<()> {
+        self.common_scalar(scalar)?;
+        self.writer.write_all(&scalar.to_repr())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PoseidonRead, PoseidonWrite};
+    use crate::halo2::{
+        arithmetic::CurveAffine,
+        transcript::{Transcript, TranscriptRead, TranscriptWrite},
+    };
+    use ff::Field;
+    use pasta_curves::{vesta, pallas::Base as Fp};
+
+    fn generator<C: CurveAffine>() -> C {
+        C::generator()
+    }
+
+    fn identity<C: CurveAffine>() -> C {
+        C::identity()
+    }
+
+    fn random_point() -> vesta::Affine {
+        (generator::<vesta::Affine>() * Fp::random(rand::thread_rng())).into()
+    }
+
+    #[test]
+    fn read_matches_write() {
+        let mut rng = rand::thread_rng();
+        let point = random_point();
+        let scalar = Fp::random(&mut rng);
+
+        let mut writer = PoseidonWrite::init(vec![]);
+        writer.write_point(point).unwrap();
+        let first = *writer.squeeze_challenge_scalar::<()>();
+        writer.write_scalar(scalar).unwrap();
+        let second = *writer.squeeze_challenge_scalar::<()>();
+        let proof = writer.finalize();
+
+        let mut reader = PoseidonRead::init(&proof[..]);
+        assert_eq!(reader.read_point().unwrap(), point);
+        assert_eq!(*reader.squeeze_challenge_scalar::<()>(), first);
+        assert_eq!(reader.read_scalar().unwrap(), scalar);
+        assert_eq!(*reader.squeeze_challenge_scalar::<()>(), second);
+        assert_ne!(first, second);
+
+        // a different message gives a different challenge
+        let mut reader = PoseidonRead::init(&proof[..]);
+        reader.common_scalar(Fp::one()).unwrap();
+        reader.read_point().unwrap();
+        assert_ne!(*reader.squeeze_challenge_scalar::<()>(), first);
+
+        assert!(PoseidonRead::init(&proof[..16]).read_point().is_err());
+        let mut writer = PoseidonWrite::init(vec![]);
+        assert!(writer.write_point(identity()).is_err());
+    }
+}
@@ -94,10 +94,11 @@ pub trait UtilitiesInstructions<F: FieldExt> {
 }
 
 //tmp hack until assigned cell is not fixed to work with poseidon api
+/// Copies a Poseidon output into a `NumericCell`, constrained equal to the output cell.
 pub fn from_cell_vale_to_numeric<F: FieldExt>(
     mut layouter: impl Layouter<F>,
     column: Column<Advice>,
-    value: Option<F>,
+    digest: CellValue<F>,
 ) -> Result<NumericCell<F>, Error>
 {
     let assigned = layouter.assign_region(
@@ -107,8 +108,9 @@ pub fn from_cell_vale_to_numeric<F: FieldExt>(
                 || "assigned",
                 column,
                 0,
-                || value.ok_or(Error::Synthesis),
+                || digest.value.ok_or(Error::Synthesis),
             )?;
+            region.constrain_equal(digest.cell, assigned.cell())?;
             Ok(Numeric::new(assigned))
         },
     )?;
@@ -140,4 +142,22 @@ impl<F: FieldExt> Var<F> for CellValue<F> {
     fn value(&self) -> Option<F> {
         self.value
     }
-}
\ No newline at end of file
+}
+
+/// Lowercase hex of a 32-byte encoding.
+pub(crate) fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes the hex of a 32-byte encoding, `None` unless it is exactly 64 hex digits.
+pub(crate) fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}